@@ -1,15 +1,49 @@
-use std::sync::atomic::{AtomicU32, Ordering};
+use std::{
+    io,
+    ops::{Add, Range},
+    path::PathBuf,
+    sync::{
+        Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Instant,
+};
 
-use image::{ImageBuffer, ImageResult, Rgb};
-use rand::random_range;
+use adaptive::{AdaptiveSampling, PixelStatistics};
+use aov::{Aov, FeatureBuffers};
+use builder::CameraBuilder;
+use checkpoint::Checkpoint;
+use diagnostics::SampleCounts;
+use image::{ImageBuffer, ImageResult, Luma, Rgb32FImage};
+use progress::{CancellationToken, ProgressObserver, ProgressTracker};
+use progressive::Progressive;
+use rayon::ThreadPoolBuilder;
+use tiles::{Tile, TileArena, TileMerger, TileSchedule};
 
 use crate::{
-    hittable::Hittable,
+    denoise::Denoiser,
+    display::DisplayTransform,
+    film::{Film, FilmTile, Radiance},
+    filter::Filter,
+    hittable::HitRecord,
     interval::Interval,
+    output::{self, OutputFormat},
     ray::Ray,
+    sampler::Sampler,
+    scene::Scene,
+    spectrum::luminance,
     vec3::{Color, Point3, Vec3},
 };
 
+pub mod adaptive;
+pub mod aov;
+pub mod builder;
+pub mod checkpoint;
+pub mod diagnostics;
+pub mod progress;
+pub mod progressive;
+pub mod tiles;
+
 pub struct Camera {
     /// Rendered image width in pixel count
     image_width: u32,
@@ -32,8 +66,14 @@ pub struct Camera {
     /// Count of random samples for each pixel
     samples_per_pixel: u16,
 
-    /// Color scale factor for a sum of pixel samples
-    pixel_samples_scale: f64,
+    /// Settings for varying the samples per pixel by their noise, if enabled
+    adaptive_sampling: Option<AdaptiveSampling>,
+
+    /// Settings for rendering in passes, saving the image after each, if enabled
+    progressive: Option<Progressive>,
+
+    /// Settings for saving the state of the render to resume from, if enabled
+    checkpoint: Option<Checkpoint>,
 
     /// Maximum number of ray bounces into scene
     max_depth: u8,
@@ -46,135 +86,516 @@ pub struct Camera {
 
     /// Defocus disk vertical radius
     defocus_disk_v: Vec3,
+
+    /// Weights of the samples for the pixels around them
+    filter: Box<dyn Filter>,
+
+    /// Generator of the numbers from which samples are built
+    sampler: Box<dyn Sampler>,
+
+    /// Seed from which the sampler derives all numbers
+    seed: u64,
+
+    /// Where the rendered image is saved, if anywhere
+    output: Option<PathBuf>,
+
+    /// Format of the rendered image, chosen by the extension of the output if not set
+    output_format: Option<OutputFormat>,
+
+    /// Conversion of the radiance to pixels of 8-bit images
+    display: DisplayTransform,
+
+    /// Extra images saved alongside the rendered image
+    aovs: Vec<Aov>,
+
+    /// Filter removing the noise from the rendered image, if enabled
+    denoiser: Option<Denoiser>,
+
+    /// Largest value of any channel of the indirect light of a sample, if limited
+    indirect_clamp: Option<f64>,
+
+    /// Whether to print the pixel of every sample discarded for a NaN or infinite value
+    log_invalid_samples: bool,
+
+    /// Division of the image over the rendering threads
+    tiles: TileSchedule,
+
+    /// Receiver of the progress of the render
+    progress: Box<dyn ProgressObserver>,
+
+    /// Token stopping the render when cancelled
+    cancellation: CancellationToken,
+}
+
+/// Everything a render produces besides the files it saves.
+#[derive(Debug, Clone)]
+pub struct RenderOutput {
+    /// Linear floating point image
+    pub image: Rgb32FImage,
+
+    /// Samples taken by this render, excluding those restored from a checkpoint
+    pub counts: SampleCounts,
 }
 
 impl Camera {
-    #[expect(clippy::too_many_arguments)]
+    /// Returns a builder for a camera, starting from the camera of the book.
     #[must_use]
-    pub fn new(
-        aspect_ratio: f64,
-        image_width: u32,
-        samples_per_pixel: u16,
-        max_depth: u8,
-        vertical_field_of_view: f64,
-        look_from: Point3,
-        look_at: Point3,
-        v_up: Vec3,
-        defocus_angle: f64,
-        focus_dist: f64,
-    ) -> Self {
-        // Calculate the image height and ensure the image height is at least 1
-        #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
-        let image_height = (f64::from(image_width) / aspect_ratio).max(1.0) as u32;
-
-        // Camera properties
-        // Viewport widths less than one are ok since they are real valued.
-        let theta = vertical_field_of_view.to_radians();
-        let h = (theta / 2.0).tan();
-        let viewport_height = 2.0 * h * focus_dist;
-        let viewport_width = viewport_height * (f64::from(image_width) / f64::from(image_height));
-        let center = look_from;
-
-        // Calculate the u, v, w unit basis vectors for the camera coordinate frame
-        let w = (look_from - look_at).unit_vector();
-        let u = v_up.cross(&w).unit_vector();
-        let v = w.cross(&u);
-
-        // Calculate the vectors across the horizontal and down the vertical viewport edges.
-        let viewport_u = viewport_width * u;
-        let viewport_v = viewport_height * -v;
-
-        let pixel_delta_u = viewport_u / f64::from(image_width);
-        let pixel_delta_v = viewport_v / f64::from(image_height);
-
-        // Calculate the location of the upper left pixel
-        let viewport_upper_left = center - focus_dist * w - viewport_u / 2.0 - viewport_v / 2.0;
-        let pixel_origin_location = viewport_upper_left + 0.5 * (pixel_delta_u + pixel_delta_v);
-
-        // Calculate the camera defocus disk basis vectors
-        let defocus_radius = focus_dist * (defocus_angle / 2.0).to_radians().tan();
-        let defocus_disk_u = u * defocus_radius;
-        let defocus_disk_v = v * defocus_radius;
-        Self {
-            image_width,
-            image_height,
-            center,
-            pixel_origin_location,
-            pixel_delta_u,
-            pixel_delta_v,
-            samples_per_pixel,
-            pixel_samples_scale: 1.0 / f64::from(samples_per_pixel),
-            max_depth,
-            defocus_angle,
-            defocus_disk_u,
-            defocus_disk_v,
-        }
+    pub fn builder() -> CameraBuilder {
+        CameraBuilder::new()
+    }
+
+    #[must_use]
+    pub fn ray_color(ray: &Ray, depth_left: u8, scene: &Scene, sampler: &mut dyn Sampler) -> Color {
+        Self::trace(ray, depth_left, scene, sampler, 0, &mut 0).total()
     }
 
-    pub fn ray_color(ray: &Ray, depth_left: u8, world: &dyn Hittable) -> Color {
+    /// Returns the light arriving along the ray, split by the number of bounces it took to reach
+    /// the camera. `bounces` is the number of surfaces the path scattered off before this ray.
+    /// Every ray traced is added to `rays`.
+    fn trace(
+        ray: &Ray,
+        depth_left: u8,
+        scene: &Scene,
+        sampler: &mut dyn Sampler,
+        bounces: u8,
+        rays: &mut u64,
+    ) -> Radiance {
         if depth_left == 0 {
-            return Color::default();
+            return Radiance::default();
         }
-        if let Some(record) = world.hit(ray, Interval::new(0.001, f64::INFINITY)) {
-            if let Some((attenuation, scattered)) = record.material().scatter(ray, &record) {
-                return attenuation * Self::ray_color(&scattered, depth_left - 1, world);
-            }
-            return Color::default();
+        *rays += 1;
+        let Some(record) = scene.world().hit(ray, Interval::new(0.001, f64::INFINITY)) else {
+            let sky = scene.sky().radiance(ray.direction().unit_vector());
+            return Radiance::after_bounces(sky, bounces);
+        };
+        let emitted = Radiance::after_bounces(record.material().emitted(ray, &record), bounces)
+            + Radiance::after_bounces(
+                Self::direct_light(ray, &record, scene, sampler, rays),
+                bounces.saturating_add(1),
+            );
+        if let Some((attenuation, scattered)) = record.material().scatter(ray, &record, sampler) {
+            let bounces = bounces.saturating_add(1);
+            return emitted
+                + attenuation
+                    * Self::trace(&scattered, depth_left - 1, scene, sampler, bounces, rays);
         }
-        let unit_direction = ray.direction().unit_vector();
-        let a = 0.5 * (unit_direction.y() + 1.0);
-        (1.0 - a) * Color::new([1.0; 3]) + a * Color::new([0.5, 0.7, 1.0])
+        emitted
     }
 
-    /// Returns the vector to a random point in the [-.5, -.5] - [+5, +5] unit square.
-    fn sample_square() -> Vec3 {
-        Vec3::new([random_range(-0.5..=0.5), random_range(-0.5..=0.5), 0.0])
+    /// Light reflected along the ray from the lights in the scene which aren't in shadow. Every
+    /// shadow ray traced is added to `rays`.
+    fn direct_light(
+        ray: &Ray,
+        record: &HitRecord,
+        scene: &Scene,
+        sampler: &mut dyn Sampler,
+        rays: &mut u64,
+    ) -> Color {
+        scene
+            .lights()
+            .iter()
+            .filter_map(|light| light.sample(record.point(), sampler))
+            .map(|sample| {
+                let reflected = record.material().evaluate(ray, record, sample.direction);
+                if reflected.near_zero() {
+                    return Color::default();
+                }
+                let shadow_ray = Ray::new(record.point(), sample.direction);
+                *rays += 1;
+                let unoccluded = Interval::new(0.001, sample.distance * (1.0 - 1e-6));
+                if scene.world().hit(&shadow_ray, unoccluded).is_some() {
+                    return Color::default();
+                }
+                reflected * sample.irradiance
+            })
+            .sum()
     }
 
-    fn defocus_disk_sample(&self) -> Point3 {
-        let point = Point3::random_in_unit_disk();
+    /// Returns the most samples any pixel can take.
+    fn max_samples(&self) -> u32 {
+        u32::from(
+            self.adaptive_sampling
+                .as_ref()
+                .map_or(self.samples_per_pixel, |adaptive| adaptive.max_samples),
+        )
+    }
+
+    /// Returns a sampler of its own for a rendering thread, set up for the seed and the samples
+    /// per pixel.
+    fn pixel_sampler(&self) -> Box<dyn Sampler> {
+        let mut sampler = self.sampler.clone_box();
+        sampler.set_seed(self.seed);
+        sampler.set_sample_count(self.max_samples());
+        sampler
+    }
+
+    /// Returns a sampled point in the [-.5, -.5] - [+5, +5] unit square.
+    fn sample_square(sampler: &mut dyn Sampler) -> [f64; 2] {
+        sampler.get_2d().map(|value| value - 0.5)
+    }
+
+    fn defocus_disk_sample(&self, sampler: &mut dyn Sampler) -> Point3 {
+        let point = Point3::sample_in_unit_disk(sampler.get_2d());
         self.center + point[0] * self.defocus_disk_u + point[1] * self.defocus_disk_v
     }
 
-    /// Construct a camera ray originating from the defocus disk and directed at a randomly sampled
-    /// point around the pixel location x, y.
-    fn get_ray(&self, x: u32, y: u32) -> Ray {
-        let offset = Self::sample_square();
+    /// Construct a camera ray originating from the defocus disk and directed at the point offset
+    /// from the pixel location x, y.
+    fn get_ray(&self, x: u32, y: u32, offset: [f64; 2], sampler: &mut dyn Sampler) -> Ray {
         let pixel_sample = self.pixel_origin_location
-            + (f64::from(x) + offset.x()) * self.pixel_delta_u
-            + (f64::from(y) + offset.y()) * self.pixel_delta_v;
+            + (f64::from(x) + offset[0]) * self.pixel_delta_u
+            + (f64::from(y) + offset[1]) * self.pixel_delta_v;
+        // Always draw the lens sample, so the following dimensions don't depend on the settings
+        let lens_sample = self.defocus_disk_sample(sampler);
         let ray_origin = if self.defocus_angle <= 0.0 {
             self.center
         } else {
-            self.defocus_disk_sample()
+            lens_sample
         };
         let ray_direction = pixel_sample - ray_origin;
         Ray::new(ray_origin, ray_direction)
     }
 
-    /// # Errors
-    /// Returns an error if the image couldn't be saved to a file.
-    pub fn render(&self, world: &(dyn Hittable + Sync)) -> ImageResult<()> {
-        let pixel_count = AtomicU32::new(0);
-        ImageBuffer::from_par_fn(self.image_width, self.image_height, |x, y| {
-            let generated_pixels = pixel_count.fetch_add(1, Ordering::Relaxed);
-            if generated_pixels % self.image_width == 0 {
-                eprint!(
-                    "\rScanlines remaining: {} ",
-                    self.image_height - (generated_pixels / self.image_width)
+    /// Takes the samples with an index in the range for the pixel x, y, as long as it needs more,
+    /// and adds them to the tile. Samples with a NaN or infinite value are discarded.
+    fn render_pixel(
+        &self,
+        [x, y]: [u32; 2],
+        sample_indices: Range<u32>,
+        statistics: &mut PixelStatistics,
+        scene: &Scene,
+        sampler: &mut dyn Sampler,
+        tile: &mut FilmTile,
+    ) -> SampleCounts {
+        let (min_samples, relative_error) = match &self.adaptive_sampling {
+            Some(adaptive) => (adaptive.min_samples, adaptive.relative_error),
+            None => (self.samples_per_pixel, 0.0),
+        };
+
+        let mut sample_index = sample_indices.start.max(statistics.next_sample());
+        let mut counts = SampleCounts::default();
+        while sample_index < sample_indices.end
+            && (sample_index < u32::from(min_samples)
+                || statistics.relative_error() > relative_error)
+        {
+            sampler.start_pixel_sample(x, y, sample_index);
+            let offset = Self::sample_square(sampler);
+            let ray = self.get_ray(x, y, offset, sampler);
+            let mut radiance =
+                Self::trace(&ray, self.max_depth, scene, sampler, 0, &mut counts.rays);
+            sample_index += 1;
+            statistics.set_next_sample(sample_index);
+            counts.taken += 1;
+
+            let total = radiance.total();
+            let channels = [total.x(), total.y(), total.z()];
+            if channels.iter().any(|channel| !channel.is_finite()) {
+                if channels.iter().any(|channel| channel.is_nan()) {
+                    counts.nan += 1;
+                } else {
+                    counts.infinite += 1;
+                }
+                if self.log_invalid_samples {
+                    eprintln!(
+                        "\rDiscarded sample {} of pixel ({x}, {y}) with value {total}",
+                        sample_index - 1
+                    );
+                }
+                tile.add_discarded_sample([x, y]);
+                continue;
+            }
+
+            if let Some(limit) = self.indirect_clamp {
+                let indirect = radiance.indirect;
+                let maximum = indirect.x().max(indirect.y()).max(indirect.z());
+                if maximum > limit {
+                    radiance.indirect = indirect * (limit / maximum);
+                    counts.clamped += 1;
+                }
+            }
+            tile.add_sample([x, y], offset, radiance, self.filter.as_ref());
+            statistics.add(luminance(radiance.total()));
+        }
+        counts
+    }
+
+    /// Takes the samples with an index in the range for every pixel of the tile with the given
+    /// index, and adds them to the film.
+    #[expect(clippy::too_many_arguments)]
+    fn render_tile(
+        &self,
+        (index, tile): (usize, &Tile),
+        arena: &mut TileArena,
+        sample_indices: &Range<u32>,
+        statistics: &Mutex<&mut [PixelStatistics]>,
+        scene: &Scene,
+        sampler: &mut dyn Sampler,
+        merger: &Mutex<TileMerger>,
+    ) -> SampleCounts {
+        // Indices of the rows of the tile in the statistics of the whole image
+        let rows = tile
+            .y_range
+            .clone()
+            .map(|y| {
+                let start = y as usize * self.image_width as usize + tile.x_range.start as usize;
+                start..start + tile.x_range.len()
+            })
+            .collect::<Vec<_>>();
+        merger.lock().unwrap().film().reset_tile(
+            &mut arena.film,
+            tile.x_range.clone(),
+            tile.y_range.clone(),
+            self.filter.as_ref(),
+        );
+        arena.statistics.clear();
+        {
+            let statistics = statistics.lock().unwrap();
+            for row in &rows {
+                arena.statistics.extend_from_slice(&statistics[row.clone()]);
+            }
+        }
+
+        let pixels = tile
+            .y_range
+            .clone()
+            .flat_map(|y| tile.x_range.clone().map(move |x| [x, y]));
+        let counts = pixels
+            .zip(&mut arena.statistics)
+            .map(|(pixel, statistics)| {
+                self.render_pixel(
+                    pixel,
+                    sample_indices.clone(),
+                    statistics,
+                    scene,
+                    sampler,
+                    &mut arena.film,
+                )
+            })
+            .fold(SampleCounts::default(), SampleCounts::add);
+
+        merger.lock().unwrap().merge(index, &mut arena.film);
+        let mut statistics = statistics.lock().unwrap();
+        for (row, tile_row) in rows
+            .into_iter()
+            .zip(arena.statistics.chunks(tile.x_range.len()))
+        {
+            statistics[row].copy_from_slice(tile_row);
+        }
+        counts
+    }
+
+    /// Takes the samples with an index in the range for every pixel and adds them to the film.
+    /// Every thread of the current pool takes the next tile in order until none are left, or the
+    /// render is cancelled.
+    fn render_pass(
+        &self,
+        tiles: &[Tile],
+        sample_indices: &Range<u32>,
+        statistics: &mut [PixelStatistics],
+        scene: &Scene,
+        film: &mut Film,
+        tracker: &ProgressTracker,
+    ) -> SampleCounts {
+        let statistics = Mutex::new(statistics);
+        let merger = Mutex::new(TileMerger::new(film));
+        let next_tile = AtomicUsize::new(0);
+        rayon::broadcast(|_| {
+            let mut sampler = self.pixel_sampler();
+            let mut arena = TileArena::default();
+            let mut counts = SampleCounts::default();
+            loop {
+                if self.cancellation.is_cancelled() {
+                    break;
+                }
+                let index = next_tile.fetch_add(1, Ordering::Relaxed);
+                let Some(tile) = tiles.get(index) else {
+                    break;
+                };
+                let tile_counts = self.render_tile(
+                    (index, tile),
+                    &mut arena,
+                    sample_indices,
+                    &statistics,
+                    scene,
+                    sampler.as_mut(),
+                    &merger,
                 );
+                tracker.add(&tile_counts);
+                counts += tile_counts;
             }
-            let pixel_color = (0..self.samples_per_pixel)
-                .map(|_| {
-                    let ray = self.get_ray(x, y);
-                    Self::ray_color(&ray, self.max_depth, world)
-                })
-                .sum::<Color>()
-                * self.pixel_samples_scale;
-            Rgb::from(pixel_color)
+            counts
         })
-        .save("image.png")?;
-        eprintln!("\rDone.                 ");
+        .into_iter()
+        .fold(SampleCounts::default(), SampleCounts::add)
+    }
+
+    /// Returns the image in the film, denoised if requested.
+    fn image(&self, film: &Film, features: Option<&FeatureBuffers>) -> Rgb32FImage {
+        match (&self.denoiser, features) {
+            (Some(denoiser), Some(features)) => {
+                let pixels = denoiser.denoise(
+                    self.image_width,
+                    self.image_height,
+                    &film.colors(),
+                    &features.normal(),
+                    &features.albedo(),
+                );
+                output::linear_image(self.image_width, self.image_height, |x, y| {
+                    pixels[y as usize * self.image_width as usize + x as usize]
+                })
+            }
+            _ => film.to_image(),
+        }
+    }
+
+    /// Saves the image to the output if set, and the heatmap of the adaptive sampling of the
+    /// film if requested.
+    fn save(&self, image: &Rgb32FImage, film: &Film) -> ImageResult<()> {
+        if let Some(path) = &self.output {
+            let format = self
+                .output_format
+                .unwrap_or_else(|| OutputFormat::from_path(path));
+            output::save(image, path, format, &self.display)?;
+        }
+
+        if let Some(AdaptiveSampling {
+            min_samples,
+            max_samples,
+            heatmap: Some(path),
+            ..
+        }) = &self.adaptive_sampling
+        {
+            let range = f64::from(max_samples - min_samples).max(1.0);
+            ImageBuffer::from_fn(self.image_width, self.image_height, |x, y| {
+                let fraction = f64::from(
+                    film.sample_count(x, y)
+                        .saturating_sub(u32::from(*min_samples)),
+                ) / range;
+                #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                Luma([(255.0 * fraction).round() as u8])
+            })
+            .save(path)?;
+        }
         Ok(())
     }
+
+    /// Renders the scene to a linear floating point image, which is also saved if an output is
+    /// set, along with the counts of the samples discarded or clamped on the way. A cancelled
+    /// render returns the image with the samples taken so far.
+    ///
+    /// # Errors
+    /// Returns an error if any of the images couldn't be saved to a file, or the rendering threads
+    /// couldn't be started.
+    ///
+    /// # Panics
+    /// Panics if rendering any of the tiles panicked.
+    pub fn render(&self, scene: &Scene) -> ImageResult<RenderOutput> {
+        let pool = ThreadPoolBuilder::new()
+            .num_threads(self.tiles.threads.unwrap_or(0))
+            .build()
+            .map_err(io::Error::other)?;
+        pool.install(|| self.render_in_pool(scene))
+    }
+
+    /// Renders the image on the threads of the current pool.
+    fn render_in_pool(&self, scene: &Scene) -> ImageResult<RenderOutput> {
+        let start = Instant::now();
+        let max_samples = self.max_samples();
+        let samples_per_pass = match (self.progressive, &self.checkpoint) {
+            (Some(progressive), _) => u32::from(progressive.samples_per_pass),
+            (None, Some(checkpoint)) => u32::from(checkpoint.samples_per_pass),
+            (None, None) => max_samples,
+        };
+
+        let fingerprint = self
+            .checkpoint
+            .as_ref()
+            .map_or(0, |_| self.fingerprint(scene));
+        let resumed = match &self.checkpoint {
+            Some(checkpoint) => {
+                checkpoint.load(self.image_width, self.image_height, fingerprint)?
+            }
+            None => None,
+        };
+        let (mut film, mut statistics, mut pass_start) = resumed.unwrap_or_else(|| {
+            (
+                Film::new(self.image_width, self.image_height),
+                vec![
+                    PixelStatistics::default();
+                    self.image_width as usize * self.image_height as usize
+                ],
+                0,
+            )
+        });
+        let tracker = ProgressTracker::new(
+            self.progress.as_ref(),
+            statistics
+                .iter()
+                .map(|statistics| u64::from(statistics.next_sample()))
+                .sum(),
+            u64::from(max_samples) * u64::from(self.image_width) * u64::from(self.image_height),
+        );
+        let tiles = self.tiles.tiles(self.image_width, self.image_height);
+        let features = (self.denoiser.is_some() || self.aovs_need_features())
+            .then(|| self.render_features(scene));
+        let mut last_checkpoint = Instant::now();
+        let mut finished = pass_start >= max_samples;
+        let mut counts = SampleCounts::default();
+        let mut cancelled = false;
+        while !finished {
+            let sample_indices = pass_start..(pass_start + samples_per_pass).min(max_samples);
+            let pass_counts = self.render_pass(
+                &tiles,
+                &sample_indices,
+                &mut statistics,
+                scene,
+                &mut film,
+                &tracker,
+            );
+            counts += pass_counts;
+            // A cancelled pass may have skipped tiles, so it is resumed from its start. Pixels
+            // which already have the samples of the pass skip them.
+            cancelled = self.cancellation.is_cancelled();
+            if !cancelled {
+                pass_start = sample_indices.end;
+                // Adaptive sampling may finish every pixel before the maximum sample count
+                finished = pass_start >= max_samples || pass_counts.taken == 0;
+            }
+
+            let out_of_time = self
+                .progressive
+                .and_then(|progressive| progressive.time_budget)
+                .is_some_and(|budget| start.elapsed() >= budget);
+            if self.progressive.is_some() && !cancelled {
+                self.save(&self.image(&film, features.as_ref()), &film)?;
+                tracker.pass_saved(pass_start);
+            }
+
+            if let Some(checkpoint) = &self.checkpoint
+                && !finished
+                && (out_of_time || cancelled || last_checkpoint.elapsed() >= checkpoint.interval)
+            {
+                checkpoint.save(fingerprint, &film, &statistics, pass_start)?;
+                last_checkpoint = Instant::now();
+            }
+            if out_of_time || cancelled {
+                break;
+            }
+        }
+
+        if finished && let Some(checkpoint) = &self.checkpoint {
+            checkpoint.remove()?;
+        }
+        let image = self.image(&film, features.as_ref());
+        if self.progressive.is_none() || cancelled {
+            self.save(&image, &film)?;
+        }
+        if let Some(path) = &self.output
+            && !self.aovs.is_empty()
+        {
+            self.save_aovs(path, &film, features.as_ref())?;
+        }
+        tracker.finish(&counts);
+        Ok(RenderOutput { image, counts })
+    }
 }
@@ -0,0 +1,114 @@
+use std::{
+    io::{self, Read, Write},
+    path::PathBuf,
+};
+
+/// Settings for spending samples where they are needed: each pixel takes samples until the
+/// estimated relative error of its luminance drops below a target.
+#[derive(Debug, Clone)]
+pub struct AdaptiveSampling {
+    /// Samples taken for every pixel before judging its error
+    pub(super) min_samples: u16,
+
+    /// Samples after which a pixel stops regardless of its error
+    pub(super) max_samples: u16,
+
+    /// Standard error of the mean relative to the mean at which a pixel stops
+    pub(super) relative_error: f64,
+
+    /// Where to save an image of the samples spent per pixel, if anywhere
+    pub(super) heatmap: Option<PathBuf>,
+}
+
+impl AdaptiveSampling {
+    #[must_use]
+    pub fn new(min_samples: u16, max_samples: u16, relative_error: f64) -> Self {
+        let min_samples = min_samples.max(2);
+        Self {
+            min_samples,
+            max_samples: max_samples.max(min_samples),
+            relative_error,
+            heatmap: None,
+        }
+    }
+
+    /// Saves an image of the samples spent per pixel, from black for the minimum to white for the
+    /// maximum sample count.
+    #[must_use]
+    pub fn with_heatmap(mut self, path: impl Into<PathBuf>) -> Self {
+        self.heatmap = Some(path.into());
+        self
+    }
+}
+
+/// Running mean and variance of the samples of a pixel, by Welford's algorithm.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PixelStatistics {
+    count: u32,
+
+    /// Index of the next sample to take, which unlike the count includes discarded samples
+    next_sample: u32,
+    mean: f64,
+
+    /// Sum of squared differences from the mean
+    squared_deviations: f64,
+}
+
+impl PixelStatistics {
+    pub fn add(&mut self, value: f64) {
+        self.count += 1;
+        let delta = value - self.mean;
+        self.mean += delta / f64::from(self.count);
+        self.squared_deviations += delta * (value - self.mean);
+    }
+
+    #[must_use]
+    pub const fn count(&self) -> u32 {
+        self.count
+    }
+
+    #[must_use]
+    pub const fn next_sample(&self) -> u32 {
+        self.next_sample
+    }
+
+    pub(super) const fn set_next_sample(&mut self, next_sample: u32) {
+        self.next_sample = next_sample;
+    }
+
+    /// Writes the statistics, to be read back by `read_from`.
+    pub(super) fn write_to(&self, writer: &mut impl Write) -> io::Result<()> {
+        writer.write_all(&self.count.to_le_bytes())?;
+        writer.write_all(&self.next_sample.to_le_bytes())?;
+        writer.write_all(&self.mean.to_le_bytes())?;
+        writer.write_all(&self.squared_deviations.to_le_bytes())
+    }
+
+    pub(super) fn read_from(reader: &mut impl Read) -> io::Result<Self> {
+        let mut count = [0; 4];
+        let mut next_sample = [0; 4];
+        let mut mean = [0; 8];
+        let mut squared_deviations = [0; 8];
+        reader.read_exact(&mut count)?;
+        reader.read_exact(&mut next_sample)?;
+        reader.read_exact(&mut mean)?;
+        reader.read_exact(&mut squared_deviations)?;
+        Ok(Self {
+            count: u32::from_le_bytes(count),
+            next_sample: u32::from_le_bytes(next_sample),
+            mean: f64::from_le_bytes(mean),
+            squared_deviations: f64::from_le_bytes(squared_deviations),
+        })
+    }
+
+    /// Standard error of the mean relative to the mean. Means close to black are compared to a
+    /// small floor instead, so black pixels don't need an endless number of samples.
+    #[must_use]
+    pub fn relative_error(&self) -> f64 {
+        if self.count < 2 {
+            return f64::INFINITY;
+        }
+        let variance = self.squared_deviations / f64::from(self.count - 1);
+        (variance / f64::from(self.count)).sqrt() / self.mean.abs().max(1e-3)
+    }
+}
@@ -0,0 +1,217 @@
+use std::path::Path;
+
+use image::{ImageResult, Rgb32FImage};
+use rayon::{
+    iter::{IndexedParallelIterator, ParallelIterator},
+    slice::ParallelSliceMut,
+};
+
+use crate::{
+    film::Film,
+    interval::Interval,
+    output::{self, OutputFormat},
+    scene::Scene,
+    vec3::{Color, Point3, Vec3},
+};
+
+use super::Camera;
+
+/// Samples per pixel for the surface features, which converge much faster than the lighting
+const FEATURE_SAMPLES: u32 = 16;
+
+/// An extra image saved alongside the rendered image, for compositing or denoising.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Aov {
+    /// Distance from the camera to the first surface, infinite for the sky
+    Depth,
+
+    /// World space shading normal of the first surface, facing the camera
+    Normal,
+
+    /// Color of the first surface
+    Albedo,
+
+    /// Number of the material of the first surface in the scene, counted from 1 in the order of
+    /// the objects using them, with zero for the sky
+    MaterialId,
+
+    /// Index plus one of the first surface's object in the scene, with zero for the sky
+    ObjectId,
+
+    /// World space position of the first surface
+    Position,
+
+    /// Light reaching the camera after at most one bounce
+    Direct,
+
+    /// Light reaching the camera after more than one bounce
+    Indirect,
+}
+
+impl Aov {
+    /// Suffix added to the name of the rendered image for this output
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Depth => "depth",
+            Self::Normal => "normal",
+            Self::Albedo => "albedo",
+            Self::MaterialId => "material_id",
+            Self::ObjectId => "object_id",
+            Self::Position => "position",
+            Self::Direct => "direct",
+            Self::Indirect => "indirect",
+        }
+    }
+}
+
+/// Properties of the first surface seen through a pixel, summed over the samples hitting one.
+#[derive(Debug, Default, Clone, Copy)]
+struct Features {
+    hits: u32,
+    depth: f64,
+    normal: Vec3,
+    albedo: Color,
+    position: Point3,
+
+    /// IDs of the first surface hit by any sample
+    material_id: u32,
+    object_id: u32,
+}
+
+impl Features {
+    fn average(&self, sum: Vec3) -> Vec3 {
+        if self.hits == 0 {
+            return Vec3::default();
+        }
+        sum / f64::from(self.hits)
+    }
+}
+
+/// Surface features of every pixel, needed for the requested outputs.
+pub(super) struct FeatureBuffers {
+    width: u32,
+    height: u32,
+    pixels: Vec<Features>,
+}
+
+impl FeatureBuffers {
+    fn image(&self, value: impl Fn(&Features) -> Color) -> Rgb32FImage {
+        output::linear_image(self.width, self.height, |x, y| {
+            value(&self.pixels[y as usize * self.width as usize + x as usize])
+        })
+    }
+
+    /// Returns the average shading normal of every pixel.
+    pub(super) fn normal(&self) -> Vec<Vec3> {
+        self.pixels
+            .iter()
+            .map(|features| features.average(features.normal))
+            .collect()
+    }
+
+    /// Returns the average albedo of every pixel.
+    pub(super) fn albedo(&self) -> Vec<Color> {
+        self.pixels
+            .iter()
+            .map(|features| features.average(features.albedo))
+            .collect()
+    }
+}
+
+impl Camera {
+    /// Whether any of the requested outputs needs the surface features.
+    pub(super) fn aovs_need_features(&self) -> bool {
+        self.aovs
+            .iter()
+            .any(|aov| !matches!(aov, Aov::Direct | Aov::Indirect))
+    }
+
+    /// Finds the first surface along the same rays as the first samples of every pixel.
+    pub(super) fn render_features(&self, scene: &Scene) -> FeatureBuffers {
+        let mut pixels =
+            vec![Features::default(); self.image_width as usize * self.image_height as usize];
+        pixels
+            .par_chunks_mut(self.image_width as usize)
+            .zip(0..self.image_height)
+            .for_each(|(row, y)| {
+                let mut sampler = self.pixel_sampler();
+                for (x, features) in (0..self.image_width).zip(row) {
+                    for sample_index in 0..FEATURE_SAMPLES {
+                        sampler.start_pixel_sample(x, y, sample_index);
+                        let offset = Self::sample_square(sampler.as_mut());
+                        let ray = self.get_ray(x, y, offset, sampler.as_mut());
+                        let Some(record) =
+                            scene.world().hit(&ray, Interval::new(0.001, f64::INFINITY))
+                        else {
+                            continue;
+                        };
+                        if features.hits == 0 {
+                            features.material_id = scene.material_id(record.material());
+                            features.object_id = record.object_id();
+                        }
+                        features.hits += 1;
+                        features.depth += record.time() * ray.direction().length();
+                        features.normal += record.normal();
+                        features.albedo += record.material().albedo(&record);
+                        features.position += record.point();
+                    }
+                }
+            });
+        FeatureBuffers {
+            width: self.image_width,
+            height: self.image_height,
+            pixels,
+        }
+    }
+
+    /// Saves the requested outputs as EXR images next to the rendered image at `output`.
+    pub(super) fn save_aovs(
+        &self,
+        output: &Path,
+        film: &Film,
+        features: Option<&FeatureBuffers>,
+    ) -> ImageResult<()> {
+        let stem = output
+            .file_stem()
+            .map_or_else(|| "image".into(), |stem| stem.to_string_lossy());
+
+        for &aov in &self.aovs {
+            let image = match (aov, features) {
+                (Aov::Direct, _) => {
+                    output::linear_image(film.width(), film.height(), |x, y| film.direct(x, y))
+                }
+                (Aov::Indirect, _) => {
+                    output::linear_image(film.width(), film.height(), |x, y| film.indirect(x, y))
+                }
+                (Aov::Depth, Some(features)) => features.image(|features| {
+                    let depth = if features.hits == 0 {
+                        f64::INFINITY
+                    } else {
+                        features.depth / f64::from(features.hits)
+                    };
+                    Color::new([depth; 3])
+                }),
+                (Aov::Normal, Some(features)) => {
+                    features.image(|features| features.average(features.normal))
+                }
+                (Aov::Albedo, Some(features)) => {
+                    features.image(|features| features.average(features.albedo))
+                }
+                (Aov::Position, Some(features)) => {
+                    features.image(|features| features.average(features.position))
+                }
+                (Aov::ObjectId, Some(features)) => {
+                    features.image(|features| Color::new([f64::from(features.object_id); 3]))
+                }
+                (Aov::MaterialId, Some(features)) => {
+                    features.image(|features| Color::new([f64::from(features.material_id); 3]))
+                }
+                (_, None) => unreachable!("features are rendered for all other outputs"),
+            };
+            let path = output.with_file_name(format!("{stem}_{}.exr", aov.name()));
+            output::save(&image, &path, OutputFormat::OpenExr, &self.display)?;
+        }
+        Ok(())
+    }
+}
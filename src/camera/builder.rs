@@ -0,0 +1,554 @@
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    path::PathBuf,
+};
+
+use crate::{
+    denoise::Denoiser,
+    display::DisplayTransform,
+    filter::{Filter, box_filter::BoxFilter},
+    output::OutputFormat,
+    sampler::{Sampler, independent::Independent},
+    vec3::{Point3, Vec3},
+};
+
+use super::{
+    Camera,
+    adaptive::AdaptiveSampling,
+    aov::Aov,
+    checkpoint::Checkpoint,
+    progress::{CancellationToken, NoProgress, ProgressObserver},
+    progressive::Progressive,
+    tiles::TileSchedule,
+};
+
+/// Reason a camera couldn't be built.
+#[derive(Debug, Clone, Copy)]
+pub enum CameraError {
+    /// The aspect ratio isn't a positive, finite number
+    InvalidAspectRatio(f64),
+
+    /// The image would have no pixels
+    ZeroImageWidth,
+
+    /// The vertical field of view isn't in (0, 180) degrees
+    FieldOfViewOutOfRange(f64),
+
+    /// The camera looks at its own position
+    ZeroViewDirection,
+
+    /// The up vector is zero, or parallel to the view direction
+    InvalidUpVector(Vec3),
+
+    /// The focus distance isn't a positive, finite number
+    InvalidFocusDistance(f64),
+
+    /// The defocus angle isn't in [0, 180) degrees
+    DefocusAngleOutOfRange(f64),
+
+    /// No samples would be taken for the pixels
+    ZeroSamplesPerPixel,
+
+    /// Rays would never enter the scene
+    ZeroMaxDepth,
+
+    /// The radius of the pixel filter isn't a positive, finite number
+    InvalidFilterRadius(f64),
+
+    /// The noise target of adaptive sampling is negative or not a number
+    InvalidRelativeError(f64),
+
+    /// The limit of the indirect light isn't a positive number
+    InvalidIndirectClamp(f64),
+
+    /// Extra images are requested without an output to save them next to
+    AovsWithoutOutput,
+}
+
+impl Display for CameraError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidAspectRatio(ratio) => {
+                write!(f, "aspect ratio {ratio} isn't a positive number")
+            }
+            Self::ZeroImageWidth => write!(f, "image width is zero"),
+            Self::FieldOfViewOutOfRange(fov) => {
+                write!(
+                    f,
+                    "vertical field of view {fov} isn't between 0 and 180 degrees"
+                )
+            }
+            Self::ZeroViewDirection => write!(f, "camera looks at its own position"),
+            Self::InvalidUpVector(up) => {
+                write!(
+                    f,
+                    "up vector {up} is zero or parallel to the view direction"
+                )
+            }
+            Self::InvalidFocusDistance(distance) => {
+                write!(f, "focus distance {distance} isn't a positive number")
+            }
+            Self::DefocusAngleOutOfRange(angle) => {
+                write!(f, "defocus angle {angle} isn't between 0 and 180 degrees")
+            }
+            Self::ZeroSamplesPerPixel => write!(f, "samples per pixel is zero"),
+            Self::ZeroMaxDepth => write!(f, "maximum depth is zero"),
+            Self::InvalidFilterRadius(radius) => {
+                write!(f, "filter radius {radius} isn't a positive number")
+            }
+            Self::InvalidRelativeError(error) => {
+                write!(f, "relative error {error} of adaptive sampling is negative")
+            }
+            Self::InvalidIndirectClamp(limit) => {
+                write!(f, "indirect clamp {limit} isn't a positive number")
+            }
+            Self::AovsWithoutOutput => write!(f, "extra images are requested without an output"),
+        }
+    }
+}
+
+impl Error for CameraError {}
+
+/// Direction the camera looks in
+#[derive(Debug, Clone, Copy)]
+enum Orientation {
+    /// Looking at a point
+    LookAt(Point3),
+
+    /// Yaw, pitch and roll in degrees, turning the camera from looking along -z
+    Euler { yaw: f64, pitch: f64, roll: f64 },
+}
+
+/// Settings of a camera, validated when the camera is built. Defaults to the camera of the book: a
+/// 100 pixel wide square image with 10 samples per pixel and 10 bounces, looking from the origin
+/// along -z with a 90 degree field of view and no defocus blur.
+pub struct CameraBuilder {
+    aspect_ratio: f64,
+    image_width: u32,
+    samples_per_pixel: u16,
+    max_depth: u8,
+    vertical_field_of_view: f64,
+    look_from: Point3,
+    orientation: Orientation,
+    v_up: Vec3,
+    defocus_angle: f64,
+    focus_distance: f64,
+    filter: Box<dyn Filter>,
+    sampler: Box<dyn Sampler>,
+    seed: u64,
+    adaptive_sampling: Option<AdaptiveSampling>,
+    progressive: Option<Progressive>,
+    checkpoint: Option<Checkpoint>,
+    output: Option<PathBuf>,
+    output_format: Option<OutputFormat>,
+    display: DisplayTransform,
+    aovs: Vec<Aov>,
+    denoiser: Option<Denoiser>,
+    indirect_clamp: Option<f64>,
+    log_invalid_samples: bool,
+    tiles: TileSchedule,
+    progress: Box<dyn ProgressObserver>,
+    cancellation: CancellationToken,
+}
+
+impl Default for CameraBuilder {
+    fn default() -> Self {
+        Self {
+            aspect_ratio: 1.0,
+            image_width: 100,
+            samples_per_pixel: 10,
+            max_depth: 10,
+            vertical_field_of_view: 90.0,
+            look_from: Point3::new([0.0, 0.0, 0.0]),
+            orientation: Orientation::LookAt(Point3::new([0.0, 0.0, -1.0])),
+            v_up: Vec3::new([0.0, 1.0, 0.0]),
+            defocus_angle: 0.0,
+            focus_distance: 10.0,
+            filter: Box::new(BoxFilter::default()),
+            sampler: Box::new(Independent::new()),
+            seed: 0,
+            adaptive_sampling: None,
+            progressive: None,
+            checkpoint: None,
+            output: None,
+            output_format: None,
+            display: DisplayTransform::default(),
+            aovs: Vec::new(),
+            denoiser: None,
+            indirect_clamp: None,
+            log_invalid_samples: false,
+            tiles: TileSchedule::default(),
+            progress: Box::new(NoProgress),
+            cancellation: CancellationToken::new(),
+        }
+    }
+}
+
+impl CameraBuilder {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ratio of image width over height
+    #[must_use]
+    pub const fn with_aspect_ratio(mut self, aspect_ratio: f64) -> Self {
+        self.aspect_ratio = aspect_ratio;
+        self
+    }
+
+    /// Rendered image width in pixels. The height follows from the aspect ratio.
+    #[must_use]
+    pub const fn with_image_width(mut self, image_width: u32) -> Self {
+        self.image_width = image_width;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_samples_per_pixel(mut self, samples_per_pixel: u16) -> Self {
+        self.samples_per_pixel = samples_per_pixel;
+        self
+    }
+
+    /// Maximum number of ray bounces into the scene
+    #[must_use]
+    pub const fn with_max_depth(mut self, max_depth: u8) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Vertical view angle in degrees
+    #[must_use]
+    pub const fn with_vertical_field_of_view(mut self, degrees: f64) -> Self {
+        self.vertical_field_of_view = degrees;
+        self
+    }
+
+    /// Position of the camera
+    #[must_use]
+    pub const fn with_look_from(mut self, look_from: Point3) -> Self {
+        self.look_from = look_from;
+        self
+    }
+
+    /// Points the camera at the point, replacing any Euler angles.
+    #[must_use]
+    pub const fn with_look_at(mut self, look_at: Point3) -> Self {
+        self.orientation = Orientation::LookAt(look_at);
+        self
+    }
+
+    /// Direction which points up in the world, +y by default. It points up in the image when
+    /// looking at a point, and is the axis of the yaw for Euler angles.
+    #[must_use]
+    pub const fn with_v_up(mut self, v_up: Vec3) -> Self {
+        self.v_up = v_up;
+        self
+    }
+
+    /// Orients the camera by angles in degrees instead of a point to look at. Starting from
+    /// looking along -z, kept perpendicular to the up vector, the yaw turns the camera left
+    /// around the up vector, the pitch tilts it up, and the roll turns the top of the image to the
+    /// right.
+    #[must_use]
+    pub const fn with_euler_angles(mut self, yaw: f64, pitch: f64, roll: f64) -> Self {
+        self.orientation = Orientation::Euler { yaw, pitch, roll };
+        self
+    }
+
+    /// Variation angle of rays through each pixel in degrees, 0 for a pinhole camera
+    #[must_use]
+    pub const fn with_defocus_angle(mut self, degrees: f64) -> Self {
+        self.defocus_angle = degrees;
+        self
+    }
+
+    /// Distance from the camera to the plane of perfect focus
+    #[must_use]
+    pub const fn with_focus_distance(mut self, focus_distance: f64) -> Self {
+        self.focus_distance = focus_distance;
+        self
+    }
+
+    /// Replaces the pixel reconstruction filter, which averages the samples within each pixel by
+    /// default.
+    #[must_use]
+    pub fn with_filter(mut self, filter: Box<dyn Filter>) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Replaces the sampler, which is independent random sampling by default.
+    #[must_use]
+    pub fn with_sampler(mut self, sampler: Box<dyn Sampler>) -> Self {
+        self.sampler = sampler;
+        self
+    }
+
+    /// Sets the seed of the sampler. Renders of the same scene with the same seed are identical.
+    #[must_use]
+    pub const fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Spends samples on each pixel until its noise drops below a target, instead of a fixed
+    /// number of samples per pixel.
+    #[must_use]
+    pub fn with_adaptive_sampling(mut self, adaptive_sampling: AdaptiveSampling) -> Self {
+        self.adaptive_sampling = Some(adaptive_sampling);
+        self
+    }
+
+    /// Renders in passes, saving the image after every pass, until the samples per pixel or the
+    /// time budget is reached.
+    #[must_use]
+    pub const fn with_progressive(mut self, progressive: Progressive) -> Self {
+        self.progressive = Some(progressive);
+        self
+    }
+
+    /// Periodically saves the state of the render between passes, and resumes from it. Renders
+    /// which aren't progressive are split into passes of 16 samples per pixel for this, which
+    /// `Checkpoint::with_samples_per_pass` changes.
+    #[must_use]
+    pub fn with_checkpoint(mut self, checkpoint: Checkpoint) -> Self {
+        self.checkpoint = Some(checkpoint);
+        self
+    }
+
+    /// Saves the rendered image to the path, besides returning it from `render`. High dynamic
+    /// range formats are chosen by the `.exr`, `.hdr` and `.pfm` extensions.
+    #[must_use]
+    pub fn with_output(mut self, path: impl Into<PathBuf>) -> Self {
+        self.output = Some(path.into());
+        self
+    }
+
+    /// Saves the rendered image in the format regardless of the extension of the output.
+    #[must_use]
+    pub const fn with_output_format(mut self, format: OutputFormat) -> Self {
+        self.output_format = Some(format);
+        self
+    }
+
+    /// Replaces the exposure and tone mapping used for 8-bit images, which clip the radiance at
+    /// 1 by default.
+    #[must_use]
+    pub const fn with_display_transform(mut self, display: DisplayTransform) -> Self {
+        self.display = display;
+        self
+    }
+
+    /// Saves extra images like depth and normals next to the rendered image, as EXR files
+    /// named after it with the name of the output appended. Requires an output.
+    #[must_use]
+    pub fn with_aovs(mut self, aovs: impl IntoIterator<Item = Aov>) -> Self {
+        self.aovs.extend(aovs);
+        self
+    }
+
+    /// Denoises the rendered image, including the snapshots of progressive renders, guided by the
+    /// normals and albedo of the surfaces.
+    #[must_use]
+    pub const fn with_denoiser(mut self, denoiser: Denoiser) -> Self {
+        self.denoiser = Some(denoiser);
+        self
+    }
+
+    /// Scales down the indirect light of samples where any channel exceeds the limit. This
+    /// suppresses fireflies from rare bright paths, at the cost of losing some energy.
+    #[must_use]
+    pub const fn with_indirect_clamp(mut self, limit: f64) -> Self {
+        self.indirect_clamp = Some(limit);
+        self
+    }
+
+    /// Prints the pixel and value of every sample discarded for a NaN or infinite value.
+    #[must_use]
+    pub const fn with_invalid_sample_log(mut self) -> Self {
+        self.log_invalid_samples = true;
+        self
+    }
+
+    /// Replaces the size and order of the tiles and the number of threads rendering them, which
+    /// are 32 pixel tiles in a spiral from the center on every logical core by default.
+    #[must_use]
+    pub const fn with_tiles(mut self, tiles: TileSchedule) -> Self {
+        self.tiles = tiles;
+        self
+    }
+
+    /// Sets the receiver of the progress, which is ignored by default.
+    #[must_use]
+    pub fn with_progress(mut self, progress: Box<dyn ProgressObserver>) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    /// Stops the render once the token is cancelled. The image and checkpoint are saved with the
+    /// samples taken so far.
+    #[must_use]
+    pub fn with_cancellation(mut self, cancellation: CancellationToken) -> Self {
+        self.cancellation = cancellation;
+        self
+    }
+
+    /// Returns the unit vector pointing forward from the camera and the vector pointing up.
+    fn view(&self) -> Result<(Vec3, Vec3), CameraError> {
+        if self.v_up.near_zero() {
+            return Err(CameraError::InvalidUpVector(self.v_up));
+        }
+        let v_up = self.v_up.unit_vector();
+        match self.orientation {
+            Orientation::LookAt(look_at) => {
+                let forward = look_at - self.look_from;
+                if forward.near_zero() {
+                    return Err(CameraError::ZeroViewDirection);
+                }
+                if v_up.cross(&forward.unit_vector()).near_zero() {
+                    return Err(CameraError::InvalidUpVector(self.v_up));
+                }
+                Ok((forward.unit_vector(), self.v_up))
+            }
+            Orientation::Euler { yaw, pitch, roll } => {
+                // Level view direction before turning the camera, -z made perpendicular to up
+                let minus_z = Vec3::new([0.0, 0.0, -1.0]);
+                let level = minus_z - minus_z.dot(&v_up) * v_up;
+                if level.near_zero() {
+                    return Err(CameraError::InvalidUpVector(self.v_up));
+                }
+                let level = level.unit_vector();
+
+                let (yaw, pitch, roll) = (yaw.to_radians(), pitch.to_radians(), roll.to_radians());
+                let turned = yaw.cos() * level + yaw.sin() * v_up.cross(&level);
+                let forward = pitch.cos() * turned + pitch.sin() * v_up;
+                let up = pitch.cos() * v_up - pitch.sin() * turned;
+                let right = forward.cross(&up);
+                Ok((forward, roll.cos() * up + roll.sin() * right))
+            }
+        }
+    }
+
+    /// Checks the settings other than the geometry of the camera.
+    fn validate_rendering(&self) -> Result<(), CameraError> {
+        if self.samples_per_pixel == 0 {
+            return Err(CameraError::ZeroSamplesPerPixel);
+        }
+        if self.max_depth == 0 {
+            return Err(CameraError::ZeroMaxDepth);
+        }
+        let radius = self.filter.radius();
+        if !(radius.is_finite() && radius > 0.0) {
+            return Err(CameraError::InvalidFilterRadius(radius));
+        }
+        if let Some(adaptive_sampling) = &self.adaptive_sampling
+            && (adaptive_sampling.relative_error.is_nan() || adaptive_sampling.relative_error < 0.0)
+        {
+            return Err(CameraError::InvalidRelativeError(
+                adaptive_sampling.relative_error,
+            ));
+        }
+        if let Some(limit) = self.indirect_clamp
+            && (limit.is_nan() || limit <= 0.0)
+        {
+            return Err(CameraError::InvalidIndirectClamp(limit));
+        }
+        if !self.aovs.is_empty() && self.output.is_none() {
+            return Err(CameraError::AovsWithoutOutput);
+        }
+        Ok(())
+    }
+
+    /// Validates the settings and builds the camera.
+    ///
+    /// # Errors
+    /// Returns an error if any of the settings is out of range, the orientation of the camera is
+    /// ambiguous, or extra images are requested without an output.
+    pub fn build(self) -> Result<Camera, CameraError> {
+        if !(self.aspect_ratio.is_finite() && self.aspect_ratio > 0.0) {
+            return Err(CameraError::InvalidAspectRatio(self.aspect_ratio));
+        }
+        if self.image_width == 0 {
+            return Err(CameraError::ZeroImageWidth);
+        }
+        if !(self.vertical_field_of_view > 0.0 && self.vertical_field_of_view < 180.0) {
+            return Err(CameraError::FieldOfViewOutOfRange(
+                self.vertical_field_of_view,
+            ));
+        }
+        if !(self.focus_distance.is_finite() && self.focus_distance > 0.0) {
+            return Err(CameraError::InvalidFocusDistance(self.focus_distance));
+        }
+        if !(self.defocus_angle >= 0.0 && self.defocus_angle < 180.0) {
+            return Err(CameraError::DefocusAngleOutOfRange(self.defocus_angle));
+        }
+        let (forward, v_up) = self.view()?;
+        self.validate_rendering()?;
+
+        // Calculate the image height and ensure the image height is at least 1
+        #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let image_height = (f64::from(self.image_width) / self.aspect_ratio).max(1.0) as u32;
+
+        // Camera properties
+        // Viewport widths less than one are ok since they are real valued.
+        let theta = self.vertical_field_of_view.to_radians();
+        let h = (theta / 2.0).tan();
+        let viewport_height = 2.0 * h * self.focus_distance;
+        let viewport_width =
+            viewport_height * (f64::from(self.image_width) / f64::from(image_height));
+        let center = self.look_from;
+
+        // Calculate the u, v, w unit basis vectors for the camera coordinate frame
+        let w = -forward;
+        let u = v_up.cross(&w).unit_vector();
+        let v = w.cross(&u);
+
+        // Calculate the vectors across the horizontal and down the vertical viewport edges.
+        let viewport_u = viewport_width * u;
+        let viewport_v = viewport_height * -v;
+
+        let pixel_delta_u = viewport_u / f64::from(self.image_width);
+        let pixel_delta_v = viewport_v / f64::from(image_height);
+
+        // Calculate the location of the upper left pixel
+        let viewport_upper_left =
+            center - self.focus_distance * w - viewport_u / 2.0 - viewport_v / 2.0;
+        let pixel_origin_location = viewport_upper_left + 0.5 * (pixel_delta_u + pixel_delta_v);
+
+        // Calculate the camera defocus disk basis vectors
+        let defocus_radius = self.focus_distance * (self.defocus_angle / 2.0).to_radians().tan();
+        let defocus_disk_u = u * defocus_radius;
+        let defocus_disk_v = v * defocus_radius;
+        Ok(Camera {
+            image_width: self.image_width,
+            image_height,
+            center,
+            pixel_origin_location,
+            pixel_delta_u,
+            pixel_delta_v,
+            samples_per_pixel: self.samples_per_pixel,
+            adaptive_sampling: self.adaptive_sampling,
+            progressive: self.progressive,
+            checkpoint: self.checkpoint,
+            max_depth: self.max_depth,
+            defocus_angle: self.defocus_angle,
+            defocus_disk_u,
+            defocus_disk_v,
+            filter: self.filter,
+            sampler: self.sampler,
+            seed: self.seed,
+            output: self.output,
+            output_format: self.output_format,
+            display: self.display,
+            aovs: self.aovs,
+            denoiser: self.denoiser,
+            indirect_clamp: self.indirect_clamp,
+            log_invalid_samples: self.log_invalid_samples,
+            tiles: self.tiles,
+            progress: self.progress,
+            cancellation: self.cancellation,
+        })
+    }
+}
@@ -0,0 +1,192 @@
+use std::{
+    fmt::{self, Write as _},
+    fs::{self, File},
+    io::{self, BufReader, BufWriter, ErrorKind, Read, Write},
+    path::PathBuf,
+    time::Duration,
+};
+
+use crate::{film::Film, scene::Scene};
+
+use super::{Camera, adaptive::PixelStatistics};
+
+/// Identifies checkpoint files and the version of their layout
+const MAGIC: &[u8; 8] = b"RTCKPT04";
+
+/// Settings for periodically saving the state of a render, so a killed render can resume where
+/// it was. Samples are derived from the seed and their index, so the accumulated film and the
+/// statistics of every pixel are all the state there is.
+#[derive(Debug, Clone)]
+pub struct Checkpoint {
+    pub(super) path: PathBuf,
+
+    /// Minimum time between two checkpoints
+    pub(super) interval: Duration,
+
+    /// Samples added to every pixel between two chances to save a checkpoint, unless rendering
+    /// progressively
+    pub(super) samples_per_pass: u16,
+}
+
+impl Checkpoint {
+    /// Saves checkpoints to the path every five minutes, checking whether one is due every 16
+    /// samples per pixel. A render resumes from the checkpoint if the file exists, and removes it
+    /// once all samples are taken.
+    #[must_use]
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            interval: Duration::from_mins(5),
+            samples_per_pass: 16,
+        }
+    }
+
+    #[must_use]
+    pub const fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Checks whether a checkpoint is due after every number of samples per pixel. Fewer samples
+    /// lose less work when killed, but add the overhead of a pass over all tiles.
+    #[must_use]
+    pub fn with_samples_per_pass(mut self, samples_per_pass: u16) -> Self {
+        self.samples_per_pass = samples_per_pass.max(1);
+        self
+    }
+
+    /// Writes the state of a render to a temporary file first, so being killed while saving
+    /// keeps the previous checkpoint intact.
+    pub(super) fn save(
+        &self,
+        fingerprint: u64,
+        film: &Film,
+        statistics: &[PixelStatistics],
+        next_sample: u32,
+    ) -> io::Result<()> {
+        let temporary_path = self.path.with_extension("tmp");
+        let mut writer = BufWriter::new(File::create(&temporary_path)?);
+        writer.write_all(MAGIC)?;
+        writer.write_all(&film.width().to_le_bytes())?;
+        writer.write_all(&film.height().to_le_bytes())?;
+        writer.write_all(&fingerprint.to_le_bytes())?;
+        writer.write_all(&next_sample.to_le_bytes())?;
+        film.write_to(&mut writer)?;
+        statistics
+            .iter()
+            .try_for_each(|statistics| statistics.write_to(&mut writer))?;
+        writer.into_inner()?.sync_all()?;
+        fs::rename(temporary_path, &self.path)
+    }
+
+    /// Reads the film, the statistics of every pixel and the index of the next sample, if a
+    /// checkpoint exists. Fails if the checkpoint belongs to a render of another size, or with
+    /// another fingerprint of the settings and scene.
+    pub(super) fn load(
+        &self,
+        width: u32,
+        height: u32,
+        fingerprint: u64,
+    ) -> io::Result<Option<(Film, Vec<PixelStatistics>, u32)>> {
+        let mut reader = match File::open(&self.path) {
+            Ok(file) => BufReader::new(file),
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(error) => return Err(error),
+        };
+        let mut magic = [0; MAGIC.len()];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(io::Error::new(
+                ErrorKind::InvalidData,
+                "not a render checkpoint",
+            ));
+        }
+        let mut header = [0; 20];
+        reader.read_exact(&mut header)?;
+        let u32_at = |start: usize| {
+            u32::from_le_bytes(header[start..start + 4].try_into().expect("4 bytes"))
+        };
+        let checkpoint_fingerprint = u64::from_le_bytes(header[8..16].try_into().expect("8 bytes"));
+        if (u32_at(0), u32_at(4), checkpoint_fingerprint) != (width, height, fingerprint) {
+            return Err(io::Error::new(
+                ErrorKind::InvalidData,
+                "checkpoint belongs to a render with another size, settings or scene",
+            ));
+        }
+
+        let film = Film::read_from(width, height, &mut reader)?;
+        let statistics = (0..width as usize * height as usize)
+            .map(|_| PixelStatistics::read_from(&mut reader))
+            .collect::<io::Result<_>>()?;
+        Ok(Some((film, statistics, u32_at(16))))
+    }
+
+    /// Removes the checkpoint of a finished render.
+    pub(super) fn remove(&self) -> io::Result<()> {
+        match fs::remove_file(&self.path) {
+            Err(error) if error.kind() != ErrorKind::NotFound => Err(error),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Hashes everything written to it by 64-bit FNV-1a, which unlike the hasher of the standard
+/// library is the same for every build. Writing the debug representation of the settings and
+/// scene of a render identifies everything a checkpoint depends on. Image textures represent
+/// their pixels by a hash, so this stays small for textured scenes.
+pub(super) struct Fingerprint(u64);
+
+impl Fingerprint {
+    pub(super) const fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+
+    pub(super) const fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+impl fmt::Write for Fingerprint {
+    fn write_str(&mut self, text: &str) -> fmt::Result {
+        for byte in text.bytes() {
+            self.0 = (self.0 ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3);
+        }
+        Ok(())
+    }
+}
+
+impl Camera {
+    /// Returns a hash of everything which determines the samples of a render, so a checkpoint is
+    /// only resumed by the same render.
+    pub(super) fn fingerprint(&self, scene: &Scene) -> u64 {
+        let mut fingerprint = Fingerprint::new();
+        let adaptive = self.adaptive_sampling.as_ref().map(|adaptive| {
+            (
+                adaptive.min_samples,
+                adaptive.max_samples,
+                adaptive.relative_error,
+            )
+        });
+        write!(
+            fingerprint,
+            "{:?}",
+            (
+                [self.image_width, self.image_height],
+                [
+                    self.center,
+                    self.pixel_origin_location,
+                    self.pixel_delta_u,
+                    self.pixel_delta_v,
+                    self.defocus_disk_u,
+                    self.defocus_disk_v,
+                ],
+                (self.samples_per_pixel, adaptive, self.max_depth),
+                (self.defocus_angle, self.indirect_clamp, self.seed),
+                (&self.filter, &self.sampler),
+                (scene.world(), scene.lights(), scene.sky()),
+            )
+        )
+        .expect("hashing can't fail");
+        fingerprint.finish()
+    }
+}
@@ -0,0 +1,52 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    ops::{Add, AddAssign},
+};
+
+/// Counts of the samples taken during a render, and of those needing correction.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SampleCounts {
+    pub taken: u64,
+
+    /// Samples discarded for carrying a NaN
+    pub nan: u64,
+
+    /// Samples discarded for carrying an infinite value
+    pub infinite: u64,
+
+    /// Samples of which the indirect light was clamped to suppress fireflies
+    pub clamped: u64,
+
+    /// Camera, scattered and shadow rays traced for the samples
+    pub rays: u64,
+}
+
+impl Add for SampleCounts {
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+
+impl AddAssign for SampleCounts {
+    fn add_assign(&mut self, rhs: Self) {
+        self.taken += rhs.taken;
+        self.nan += rhs.nan;
+        self.infinite += rhs.infinite;
+        self.clamped += rhs.clamped;
+        self.rays += rhs.rays;
+    }
+}
+
+impl Display for SampleCounts {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} samples taken with {} rays, {} discarded as NaN, {} discarded as infinite, {} with \
+             clamped indirect light",
+            self.taken, self.rays, self.nan, self.infinite, self.clamped
+        )
+    }
+}
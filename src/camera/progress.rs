@@ -0,0 +1,160 @@
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicU64, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use super::diagnostics::SampleCounts;
+
+/// State of a running render.
+#[derive(Debug, Clone, Copy)]
+pub struct Progress {
+    pub completed_samples: u64,
+
+    /// Samples of the whole render. Adaptive sampling may finish with fewer.
+    pub total_samples: u64,
+
+    /// Camera, scattered and shadow rays traced per second since the render started
+    pub rays_per_second: f64,
+    pub elapsed: Duration,
+
+    /// Estimated time until all samples are taken, once any samples have been taken
+    pub remaining: Option<Duration>,
+}
+
+/// Receives the progress of a render, from any of the rendering threads.
+pub trait ProgressObserver: Send + Sync {
+    /// Called whenever a tile is finished.
+    fn update(&self, progress: &Progress);
+
+    /// Called whenever the image of a progressive render is saved.
+    fn pass_saved(&self, _samples_per_pixel: u32) {}
+
+    /// Called once the render stops, whether finished or cancelled.
+    fn finish(&self, _progress: &Progress, _counts: &SampleCounts) {}
+}
+
+/// Ignores the progress. The counts of the samples are still returned by `Camera::render`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoProgress;
+
+impl ProgressObserver for NoProgress {
+    fn update(&self, _progress: &Progress) {}
+}
+
+/// Prints the progress to standard error.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ConsoleProgress;
+
+impl ProgressObserver for ConsoleProgress {
+    #[expect(clippy::cast_precision_loss)]
+    fn update(&self, progress: &Progress) {
+        let percentage =
+            100.0 * progress.completed_samples as f64 / progress.total_samples.max(1) as f64;
+        let remaining = progress.remaining.map_or_else(
+            || "?".to_owned(),
+            |remaining| remaining.as_secs().to_string(),
+        );
+        eprint!(
+            "\r{percentage:5.1}% {:.2} Mrays/s, {remaining}s remaining   ",
+            progress.rays_per_second / 1e6
+        );
+    }
+
+    fn pass_saved(&self, samples_per_pixel: u32) {
+        eprint!("\rSaved pass with {samples_per_pixel} samples per pixel ");
+    }
+
+    fn finish(&self, progress: &Progress, counts: &SampleCounts) {
+        eprintln!(
+            "\rDone in {:.1?}.                              ",
+            progress.elapsed
+        );
+        eprintln!("{counts}");
+    }
+}
+
+/// Lets another thread stop a render. The render finishes the tiles being rendered and saves the
+/// image and checkpoint, so it can be resumed.
+#[derive(Debug, Default, Clone)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Sums the work of all threads and reports it to an observer.
+pub(super) struct ProgressTracker<'a> {
+    observer: &'a dyn ProgressObserver,
+    start: Instant,
+
+    /// Samples taken before this run, when resuming from a checkpoint
+    resumed_samples: u64,
+    total_samples: u64,
+    samples: AtomicU64,
+    rays: AtomicU64,
+}
+
+impl<'a> ProgressTracker<'a> {
+    pub(super) fn new(
+        observer: &'a dyn ProgressObserver,
+        resumed_samples: u64,
+        total_samples: u64,
+    ) -> Self {
+        Self {
+            observer,
+            start: Instant::now(),
+            resumed_samples,
+            total_samples,
+            samples: AtomicU64::new(0),
+            rays: AtomicU64::new(0),
+        }
+    }
+
+    pub(super) fn add(&self, counts: &SampleCounts) {
+        self.samples.fetch_add(counts.taken, Ordering::Relaxed);
+        self.rays.fetch_add(counts.rays, Ordering::Relaxed);
+        self.observer.update(&self.progress());
+    }
+
+    pub(super) fn pass_saved(&self, samples_per_pixel: u32) {
+        self.observer.pass_saved(samples_per_pixel);
+    }
+
+    pub(super) fn finish(&self, counts: &SampleCounts) {
+        self.observer.finish(&self.progress(), counts);
+    }
+
+    #[expect(clippy::cast_precision_loss)]
+    fn progress(&self) -> Progress {
+        let elapsed = self.start.elapsed();
+        let samples = self.samples.load(Ordering::Relaxed);
+        let rays = self.rays.load(Ordering::Relaxed);
+        let completed_samples = self.resumed_samples + samples;
+        let remaining = (samples > 0).then(|| {
+            let remaining_samples = self.total_samples.saturating_sub(completed_samples);
+            elapsed.mul_f64(remaining_samples as f64 / samples as f64)
+        });
+        Progress {
+            completed_samples,
+            total_samples: self.total_samples,
+            rays_per_second: rays as f64 / elapsed.as_secs_f64().max(1e-9),
+            elapsed,
+            remaining,
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use std::time::Duration;
+
+/// Settings for rendering in passes of a few samples per pixel, saving the image after every pass
+/// so long renders can be previewed and stopped early.
+#[derive(Debug, Clone, Copy)]
+pub struct Progressive {
+    /// Samples added to every pixel in each pass
+    pub(super) samples_per_pass: u16,
+
+    /// Wall-clock time after which no new pass is started, if limited
+    pub(super) time_budget: Option<Duration>,
+}
+
+impl Progressive {
+    #[must_use]
+    pub fn new(samples_per_pass: u16) -> Self {
+        Self {
+            samples_per_pass: samples_per_pass.max(1),
+            time_budget: None,
+        }
+    }
+
+    /// Stops starting new passes once the render has taken this long, even if the samples per
+    /// pixel haven't been reached yet.
+    #[must_use]
+    pub const fn with_time_budget(mut self, time_budget: Duration) -> Self {
+        self.time_budget = Some(time_budget);
+        self
+    }
+}
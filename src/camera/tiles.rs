@@ -0,0 +1,169 @@
+use std::{collections::BTreeMap, f64::consts::PI, ops::Range};
+
+use crate::film::{Film, FilmTile};
+
+use super::adaptive::PixelStatistics;
+
+/// Order in which the tiles of the image are rendered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TileOrder {
+    /// Row by row from the top left
+    Scanline,
+
+    /// Outwards from the center, so the subject appears first
+    Spiral,
+
+    /// Along a Hilbert curve, keeping consecutive tiles next to each other
+    Hilbert,
+}
+
+/// Settings for splitting the image into tiles, which threads render one at a time.
+#[derive(Debug, Clone, Copy)]
+pub struct TileSchedule {
+    /// Width and height of a tile in pixels
+    pub(super) tile_size: u32,
+    pub(super) order: TileOrder,
+
+    /// Number of rendering threads, one per logical core if not set
+    pub(super) threads: Option<usize>,
+}
+
+impl TileSchedule {
+    #[must_use]
+    pub fn new(tile_size: u32, order: TileOrder) -> Self {
+        Self {
+            tile_size: tile_size.max(1),
+            order,
+            threads: None,
+        }
+    }
+
+    #[must_use]
+    pub const fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = Some(threads);
+        self
+    }
+
+    /// Splits an image into tiles, in the order to render them.
+    pub(super) fn tiles(&self, width: u32, height: u32) -> Vec<Tile> {
+        let columns = width.div_ceil(self.tile_size);
+        let rows = height.div_ceil(self.tile_size);
+        let mut coordinates = (0..rows)
+            .flat_map(|row| (0..columns).map(move |column| [column, row]))
+            .collect::<Vec<_>>();
+        match self.order {
+            TileOrder::Scanline => {}
+            TileOrder::Spiral => {
+                let center = [columns, rows].map(|count| f64::from(count - 1) / 2.0);
+                coordinates.sort_by(|left, right| {
+                    spiral_key(*left, center).total_cmp(&spiral_key(*right, center))
+                });
+            }
+            TileOrder::Hilbert => {
+                let size = columns.max(rows).next_power_of_two();
+                coordinates.sort_by_key(|&coordinates| hilbert_index(size, coordinates));
+            }
+        }
+        coordinates
+            .into_iter()
+            .map(|[column, row]| Tile {
+                x_range: column * self.tile_size..((column + 1) * self.tile_size).min(width),
+                y_range: row * self.tile_size..((row + 1) * self.tile_size).min(height),
+            })
+            .collect()
+    }
+}
+
+impl Default for TileSchedule {
+    fn default() -> Self {
+        Self::new(32, TileOrder::Spiral)
+    }
+}
+
+/// Sorts tiles by the square ring around the center they lie on, then by their angle within it.
+fn spiral_key([column, row]: [u32; 2], center: [f64; 2]) -> f64 {
+    let offset = [f64::from(column) - center[0], f64::from(row) - center[1]];
+    let ring = offset[0].abs().max(offset[1].abs()).round();
+    let angle = offset[1].atan2(offset[0]) + PI;
+    ring * 2.0 * PI + angle
+}
+
+/// Position of a cell along the Hilbert curve filling a square grid of a power of two size.
+fn hilbert_index(size: u32, [mut x, mut y]: [u32; 2]) -> u64 {
+    let mut index = 0;
+    let mut scale = size / 2;
+    while scale > 0 {
+        let rx = u32::from(x & scale > 0);
+        let ry = u32::from(y & scale > 0);
+        index += u64::from(scale) * u64::from(scale) * u64::from((3 * rx) ^ ry);
+        // Rotate the quadrant so the curve continues where the previous one ended
+        if ry == 0 {
+            if rx == 1 {
+                x = size - 1 - x;
+                y = size - 1 - y;
+            }
+            std::mem::swap(&mut x, &mut y);
+        }
+        scale /= 2;
+    }
+    index
+}
+
+/// A rectangle of pixels rendered by a single thread.
+#[derive(Debug, Clone)]
+pub(super) struct Tile {
+    pub(super) x_range: Range<u32>,
+    pub(super) y_range: Range<u32>,
+}
+
+/// Memory a thread reuses for every tile it renders.
+#[derive(Debug, Default)]
+pub(super) struct TileArena {
+    pub(super) film: FilmTile,
+
+    /// Statistics of the pixels of the tile, row by row
+    pub(super) statistics: Vec<PixelStatistics>,
+}
+
+/// Merges rendered tiles into the film in the order of their index. The filter spreads samples
+/// over the edges of their tile, so overlapping tiles must be summed in the same order for every
+/// render to give the same result, regardless of which thread finishes first.
+pub(super) struct TileMerger<'a> {
+    film: &'a mut Film,
+
+    /// Index of the next tile to merge
+    next_tile: usize,
+
+    /// Rendered tiles waiting for the tiles before them
+    waiting: BTreeMap<usize, FilmTile>,
+
+    /// Merged tiles whose memory can be reused
+    spare: Vec<FilmTile>,
+}
+
+impl<'a> TileMerger<'a> {
+    pub(super) const fn new(film: &'a mut Film) -> Self {
+        Self {
+            film,
+            next_tile: 0,
+            waiting: BTreeMap::new(),
+            spare: Vec::new(),
+        }
+    }
+
+    pub(super) fn film(&self) -> &Film {
+        self.film
+    }
+
+    /// Merges the tile with the given index once all tiles before it are merged. Takes the tile,
+    /// leaving one to reuse in its place.
+    pub(super) fn merge(&mut self, index: usize, tile: &mut FilmTile) {
+        let tile = std::mem::replace(tile, self.spare.pop().unwrap_or_default());
+        self.waiting.insert(index, tile);
+        while let Some(tile) = self.waiting.remove(&self.next_tile) {
+            self.film.merge_tile(&tile);
+            self.spare.push(tile);
+            self.next_tile += 1;
+        }
+    }
+}
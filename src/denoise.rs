@@ -0,0 +1,163 @@
+use rayon::{
+    iter::{IndexedParallelIterator, ParallelIterator},
+    slice::ParallelSliceMut,
+};
+
+use crate::vec3::{Color, Vec3};
+
+/// Weights of the B3 spline kernel along each axis
+const KERNEL: [f64; 5] = [1.0 / 16.0, 1.0 / 4.0, 3.0 / 8.0, 1.0 / 4.0, 1.0 / 16.0];
+
+/// Most filter passes, beyond which the spacing of the kernel exceeds any image
+const MAX_ITERATIONS: u8 = 16;
+
+/// Albedo below which a channel isn't divided out of the color before filtering
+const MIN_ALBEDO: f64 = 1e-3;
+
+/// Edge-avoiding À-trous wavelet filter after Dammertz et al. It blurs the image with kernels of
+/// doubling spacing, while weighing neighbours down that differ in color, normal or albedo, so
+/// edges and textures stay sharp.
+#[derive(Debug, Clone, Copy)]
+pub struct Denoiser {
+    /// Number of filter passes, each doubling the radius
+    iterations: u8,
+
+    /// Color difference at which neighbours lose most of their weight, halved every pass
+    color_sigma: f64,
+
+    /// Normal difference at which neighbours lose most of their weight
+    normal_sigma: f64,
+
+    /// Albedo difference at which neighbours lose most of their weight
+    albedo_sigma: f64,
+}
+
+impl Denoiser {
+    /// Creates the filter with the differences of each feature at which neighbours lose most of
+    /// their weight. A sigma which isn't positive ignores the feature. The iterations are capped at
+    /// 16, as later passes would sample only outside the image.
+    #[must_use]
+    pub const fn new(
+        iterations: u8,
+        color_sigma: f64,
+        normal_sigma: f64,
+        albedo_sigma: f64,
+    ) -> Self {
+        Self {
+            iterations: if iterations > MAX_ITERATIONS {
+                MAX_ITERATIONS
+            } else {
+                iterations
+            },
+            color_sigma,
+            normal_sigma,
+            albedo_sigma,
+        }
+    }
+
+    /// Filters a linear image in reading order, guided by the normal and albedo of every pixel.
+    /// Pixels without a surface are expected to have a zero normal and albedo.
+    #[must_use]
+    pub fn denoise(
+        &self,
+        width: u32,
+        height: u32,
+        color: &[Color],
+        normal: &[Vec3],
+        albedo: &[Color],
+    ) -> Vec<Color> {
+        let width = width as usize;
+        let height = height as usize;
+
+        // Filter the lighting rather than the textured color, so textures aren't blurred
+        let divisor = |albedo: Color| {
+            Color::new(
+                [albedo.x(), albedo.y(), albedo.z()]
+                    .map(|channel| if channel > MIN_ALBEDO { channel } else { 1.0 }),
+            )
+        };
+        let mut current = color
+            .iter()
+            .zip(albedo)
+            .map(|(&color, &albedo)| color / divisor(albedo))
+            .collect::<Vec<_>>();
+        let mut next = vec![Color::default(); current.len()];
+
+        for iteration in 0..self.iterations {
+            let step = 1_isize << iteration;
+            let color_sigma = self.color_sigma * 0.5_f64.powi(i32::from(iteration));
+            next.par_chunks_mut(width)
+                .zip(0..height)
+                .for_each(|(row, y)| {
+                    for (x, output) in row.iter_mut().enumerate() {
+                        let center = y * width + x;
+                        let center_color = compress(current[center]);
+                        let mut sum = Color::default();
+                        let mut weight_sum = 0.0;
+                        for (kernel_y, offset_y) in KERNEL.iter().zip(-2_isize..) {
+                            let Some(sample_y) = y.checked_add_signed(offset_y * step) else {
+                                continue;
+                            };
+                            if sample_y >= height {
+                                continue;
+                            }
+                            for (kernel_x, offset_x) in KERNEL.iter().zip(-2_isize..) {
+                                let Some(sample_x) = x.checked_add_signed(offset_x * step) else {
+                                    continue;
+                                };
+                                if sample_x >= width {
+                                    continue;
+                                }
+                                let sample = sample_y * width + sample_x;
+                                let distance = |left: Vec3, right: Vec3, sigma: f64| {
+                                    if sigma > 0.0 {
+                                        (left - right).length_squared() / (sigma * sigma)
+                                    } else {
+                                        0.0
+                                    }
+                                };
+                                let weight = kernel_x
+                                    * kernel_y
+                                    * (-distance(
+                                        center_color,
+                                        compress(current[sample]),
+                                        color_sigma,
+                                    ) - distance(
+                                        normal[center],
+                                        normal[sample],
+                                        self.normal_sigma,
+                                    ) - distance(
+                                        albedo[center],
+                                        albedo[sample],
+                                        self.albedo_sigma,
+                                    ))
+                                    .exp();
+                                sum += weight * current[sample];
+                                weight_sum += weight;
+                            }
+                        }
+                        *output = sum / weight_sum;
+                    }
+                });
+            std::mem::swap(&mut current, &mut next);
+        }
+
+        current
+            .into_iter()
+            .zip(albedo)
+            .map(|(color, &albedo)| color * divisor(albedo))
+            .collect()
+    }
+}
+
+impl Default for Denoiser {
+    fn default() -> Self {
+        Self::new(5, 0.2, 0.3, 0.1)
+    }
+}
+
+/// Compresses radiance into [0, 1) per channel, so color differences in bright areas don't
+/// dominate.
+fn compress(color: Color) -> Color {
+    Color::new([color.x(), color.y(), color.z()].map(|channel| channel / (1.0 + channel.max(0.0))))
+}
@@ -0,0 +1,170 @@
+use image::Rgb;
+
+use crate::{spectrum::luminance, vec3::Color};
+
+/// Curve compressing linear radiance into the displayable range.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ToneMapping {
+    /// Clips every channel at 1
+    Clamp,
+
+    /// Maps luminance L to L / (1 + L), keeping the hue
+    Reinhard,
+
+    /// Reinhard, reaching white at the given luminance instead of at infinity
+    ExtendedReinhard { white: f64 },
+
+    /// Narkowicz's fit of the ACES filmic curve, per channel
+    Aces,
+
+    /// Sobotka's `AgX`, which desaturates bright colors towards white instead of skewing their hue
+    AgX,
+}
+
+impl ToneMapping {
+    /// Maps linear radiance to linear display values in [0, 1].
+    #[must_use]
+    pub fn apply(self, color: Color) -> Color {
+        match self {
+            Self::Clamp => color,
+            Self::Reinhard => scale_luminance(color, |luminance| luminance / (1.0 + luminance)),
+            Self::ExtendedReinhard { white } => scale_luminance(color, |luminance| {
+                luminance * (1.0 + luminance / (white * white)) / (1.0 + luminance)
+            }),
+            Self::Aces => Color::new([color.x(), color.y(), color.z()].map(aces)),
+            Self::AgX => agx(color),
+        }
+    }
+}
+
+/// Scales the color to give it the mapped luminance.
+fn scale_luminance(color: Color, map: impl Fn(f64) -> f64) -> Color {
+    let luminance = luminance(color);
+    if luminance <= 0.0 {
+        return Color::default();
+    }
+    color * (map(luminance) / luminance)
+}
+
+fn aces(value: f64) -> f64 {
+    (value * (2.51 * value + 0.03)) / (value * (2.43 * value + 0.59) + 0.14)
+}
+
+/// `AgX` with the default look, after Sobotka's reference and Wrensch's polynomial fit of its
+/// contrast curve.
+fn agx(color: Color) -> Color {
+    const MIN_EV: f64 = -12.473_931_188;
+    const MAX_EV: f64 = 4.026_068_812;
+
+    // Inset into the AgX working space
+    let inset = multiply(
+        [
+            [
+                0.842_479_062_253_094,
+                0.078_433_599_999_999_2,
+                0.079_223_745_147_764_3,
+            ],
+            [
+                0.042_328_242_261_012_3,
+                0.878_468_636_469_772,
+                0.079_166_127_460_543_4,
+            ],
+            [0.042_375_654_905_705_1, 0.078_433_6, 0.879_142_973_793_104],
+        ],
+        color,
+    );
+    let curved = [inset.x(), inset.y(), inset.z()].map(|value| {
+        let encoded = (value.max(1e-10).log2().clamp(MIN_EV, MAX_EV) - MIN_EV) / (MAX_EV - MIN_EV);
+        contrast(encoded)
+    });
+    let outset = multiply(
+        [
+            [
+                1.196_879_005_120_17,
+                -0.098_020_881_140_136_8,
+                -0.099_029_744_079_720_5,
+            ],
+            [
+                -0.052_896_851_757_456_2,
+                1.151_903_129_904_17,
+                -0.098_961_176_844_843_3,
+            ],
+            [
+                -0.052_971_635_514_443_8,
+                -0.098_043_450_117_124_1,
+                1.151_073_672_641_16,
+            ],
+        ],
+        Color::new(curved),
+    );
+    // The curve produces values encoded for a 2.2 gamma display
+    Color::new([outset.x(), outset.y(), outset.z()].map(|value| value.max(0.0).powf(2.2)))
+}
+
+fn contrast(x: f64) -> f64 {
+    let x2 = x * x;
+    let x4 = x2 * x2;
+    15.5 * x4 * x2 - 40.14 * x4 * x + 31.96 * x4 - 6.868 * x2 * x + 0.4298 * x2 + 0.1191 * x
+        - 0.002_32
+}
+
+fn multiply(matrix: [[f64; 3]; 3], color: Color) -> Color {
+    Color::new(matrix.map(|row| row[0] * color.x() + row[1] * color.y() + row[2] * color.z()))
+}
+
+/// The piecewise sRGB transfer function, encoding a linear value in [0, 1] for display.
+#[must_use]
+pub fn srgb_oetf(linear: f64) -> f64 {
+    if linear <= 0.003_130_8 {
+        12.92 * linear
+    } else {
+        1.055 * linear.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// Turns linear radiance into 8-bit sRGB pixels: exposure, then tone mapping, then the sRGB
+/// transfer function.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DisplayTransform {
+    /// Exposure adjustment in stops, each doubling the radiance
+    exposure: f64,
+    tone_mapping: ToneMapping,
+}
+
+impl DisplayTransform {
+    #[must_use]
+    pub const fn new(tone_mapping: ToneMapping) -> Self {
+        Self {
+            exposure: 0.0,
+            tone_mapping,
+        }
+    }
+
+    #[must_use]
+    pub const fn with_exposure(mut self, stops: f64) -> Self {
+        self.exposure = stops;
+        self
+    }
+
+    /// Returns the color encoded for display, with every channel in [0, 1].
+    #[must_use]
+    pub fn encode(&self, color: Color) -> Color {
+        let mapped = self.tone_mapping.apply(color * self.exposure.exp2());
+        Color::new(
+            [mapped.x(), mapped.y(), mapped.z()].map(|value| srgb_oetf(value.clamp(0.0, 1.0))),
+        )
+    }
+
+    #[must_use]
+    #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn apply(&self, color: Color) -> Rgb<u8> {
+        let encoded = self.encode(color);
+        Rgb([encoded.x(), encoded.y(), encoded.z()].map(|value| (255.0 * value).round() as u8))
+    }
+}
+
+impl Default for DisplayTransform {
+    fn default() -> Self {
+        Self::new(ToneMapping::Clamp)
+    }
+}
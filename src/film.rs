@@ -0,0 +1,333 @@
+use std::{
+    io::{self, Read, Write},
+    ops::{Add, Mul, Range},
+};
+
+use image::Rgb32FImage;
+
+use crate::{filter::Filter, output, vec3::Color};
+
+/// Light carried by a sample, split into the light reaching the camera after at most one bounce
+/// and the rest.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Radiance {
+    pub direct: Color,
+    pub indirect: Color,
+}
+
+impl Radiance {
+    /// Light reaching the camera along a path scattered by the given number of surfaces.
+    #[must_use]
+    pub fn after_bounces(color: Color, bounces: u8) -> Self {
+        if bounces <= 1 {
+            Self {
+                direct: color,
+                indirect: Color::default(),
+            }
+        } else {
+            Self {
+                direct: Color::default(),
+                indirect: color,
+            }
+        }
+    }
+
+    #[must_use]
+    pub fn total(self) -> Color {
+        self.direct + self.indirect
+    }
+}
+
+impl Add for Radiance {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            direct: self.direct + rhs.direct,
+            indirect: self.indirect + rhs.indirect,
+        }
+    }
+}
+
+impl Mul<Radiance> for Color {
+    type Output = Radiance;
+
+    fn mul(self, rhs: Radiance) -> Self::Output {
+        Radiance {
+            direct: self * rhs.direct,
+            indirect: self * rhs.indirect,
+        }
+    }
+}
+
+/// Accumulated samples of a pixel
+#[derive(Debug, Default, Clone, Copy)]
+struct FilmPixel {
+    /// Sum of the direct light of the samples multiplied by their filter weight
+    weighted_direct: Color,
+
+    /// Sum of the indirect light of the samples multiplied by their filter weight
+    weighted_indirect: Color,
+
+    /// Sum of the filter weights of the samples
+    weight_sum: f64,
+
+    /// Number of samples taken through this pixel
+    sample_count: u32,
+}
+
+impl FilmPixel {
+    const BYTES: usize = 60;
+
+    fn to_bytes(self) -> [u8; Self::BYTES] {
+        let mut bytes = [0; Self::BYTES];
+        let channels = [self.weighted_direct, self.weighted_indirect]
+            .into_iter()
+            .flat_map(|color| [color.x(), color.y(), color.z()]);
+        for (value, chunk) in channels.zip(bytes[..48].chunks_exact_mut(8)) {
+            chunk.copy_from_slice(&value.to_le_bytes());
+        }
+        bytes[48..56].copy_from_slice(&self.weight_sum.to_le_bytes());
+        bytes[56..].copy_from_slice(&self.sample_count.to_le_bytes());
+        bytes
+    }
+
+    fn from_bytes(bytes: &[u8; Self::BYTES]) -> Self {
+        let f64_at =
+            |start: usize| f64::from_le_bytes(bytes[start..start + 8].try_into().expect("8 bytes"));
+        Self {
+            weighted_direct: Color::new([f64_at(0), f64_at(8), f64_at(16)]),
+            weighted_indirect: Color::new([f64_at(24), f64_at(32), f64_at(40)]),
+            weight_sum: f64_at(48),
+            sample_count: u32::from_le_bytes(bytes[56..].try_into().expect("4 bytes")),
+        }
+    }
+
+    /// Divides the weighted sum by the weights, or returns black without any samples. Filters
+    /// with negative lobes can leave a pixel with few samples a weight sum which isn't positive,
+    /// which counts as no samples, and negative channels, which are clamped to zero.
+    fn normalize(&self, weighted_sum: Color) -> Color {
+        if self.weight_sum.is_nan() || self.weight_sum <= 0.0 {
+            return Color::default();
+        }
+        let normalized = weighted_sum / self.weight_sum;
+        Color::new([normalized.x(), normalized.y(), normalized.z()].map(|channel| channel.max(0.0)))
+    }
+
+    fn merge(&mut self, other: &Self) {
+        self.weighted_direct += other.weighted_direct;
+        self.weighted_indirect += other.weighted_indirect;
+        self.weight_sum += other.weight_sum;
+        self.sample_count += other.sample_count;
+    }
+}
+
+/// The image being rendered, where every sample is spread over the pixels around it by a filter.
+#[derive(Debug, Clone)]
+pub struct Film {
+    width: u32,
+    height: u32,
+    pixels: Vec<FilmPixel>,
+}
+
+impl Film {
+    #[must_use]
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![FilmPixel::default(); width as usize * height as usize],
+        }
+    }
+
+    #[must_use]
+    pub const fn width(&self) -> u32 {
+        self.width
+    }
+
+    #[must_use]
+    pub const fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Writes the accumulated samples of all pixels, to be read back by `read_from`.
+    ///
+    /// # Errors
+    /// Returns an error if writing failed.
+    pub fn write_to(&self, writer: &mut impl Write) -> io::Result<()> {
+        self.pixels
+            .iter()
+            .try_for_each(|pixel| writer.write_all(&pixel.to_bytes()))
+    }
+
+    /// Reads a film of the given size written by `write_to`.
+    ///
+    /// # Errors
+    /// Returns an error if reading failed.
+    pub fn read_from(width: u32, height: u32, reader: &mut impl Read) -> io::Result<Self> {
+        let mut film = Self::new(width, height);
+        let mut bytes = [0; FilmPixel::BYTES];
+        for pixel in &mut film.pixels {
+            reader.read_exact(&mut bytes)?;
+            *pixel = FilmPixel::from_bytes(&bytes);
+        }
+        Ok(film)
+    }
+
+    /// Creates a tile for the samples taken through the given pixels, extended by the pixels
+    /// reached by the filter.
+    #[must_use]
+    pub fn tile(&self, x_range: Range<u32>, y_range: Range<u32>, filter: &dyn Filter) -> FilmTile {
+        let mut tile = FilmTile::default();
+        self.reset_tile(&mut tile, x_range, y_range, filter);
+        tile
+    }
+
+    /// Empties a tile and moves it to the given pixels like `tile`, reusing its memory.
+    #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn reset_tile(
+        &self,
+        tile: &mut FilmTile,
+        x_range: Range<u32>,
+        y_range: Range<u32>,
+        filter: &dyn Filter,
+    ) {
+        let padding = (filter.radius() - 0.5).ceil().max(0.0) as u32;
+        tile.x_range =
+            x_range.start.saturating_sub(padding)..(x_range.end + padding).min(self.width);
+        tile.y_range =
+            y_range.start.saturating_sub(padding)..(y_range.end + padding).min(self.height);
+        tile.pixels.clear();
+        tile.pixels.resize(
+            tile.x_range.len() * tile.y_range.len(),
+            FilmPixel::default(),
+        );
+    }
+
+    /// Adds the samples of a tile to the film.
+    pub fn merge_tile(&mut self, tile: &FilmTile) {
+        for (tile_row, y) in tile.y_range.clone().enumerate() {
+            let tile_start = tile_row * tile.x_range.len();
+            let start = self.index(tile.x_range.start, y);
+            self.pixels[start..start + tile.x_range.len()]
+                .iter_mut()
+                .zip(&tile.pixels[tile_start..tile_start + tile.x_range.len()])
+                .for_each(|(pixel, tile_pixel)| pixel.merge(tile_pixel));
+        }
+    }
+
+    /// Returns the filtered color of the pixel x, y.
+    #[must_use]
+    pub fn color(&self, x: u32, y: u32) -> Color {
+        let pixel = &self.pixels[self.index(x, y)];
+        pixel.normalize(pixel.weighted_direct + pixel.weighted_indirect)
+    }
+
+    /// Returns the filtered light of the pixel x, y which reached the camera after at most one
+    /// bounce.
+    #[must_use]
+    pub fn direct(&self, x: u32, y: u32) -> Color {
+        let pixel = &self.pixels[self.index(x, y)];
+        pixel.normalize(pixel.weighted_direct)
+    }
+
+    /// Returns the filtered light of the pixel x, y which reached the camera after more than one
+    /// bounce.
+    #[must_use]
+    pub fn indirect(&self, x: u32, y: u32) -> Color {
+        let pixel = &self.pixels[self.index(x, y)];
+        pixel.normalize(pixel.weighted_indirect)
+    }
+
+    /// Returns the filtered colors of all pixels in reading order.
+    #[must_use]
+    pub fn colors(&self) -> Vec<Color> {
+        (0..self.height)
+            .flat_map(|y| (0..self.width).map(move |x| (x, y)))
+            .map(|(x, y)| self.color(x, y))
+            .collect()
+    }
+
+    /// Returns the filtered colors of all pixels as a linear floating point image.
+    #[must_use]
+    pub fn to_image(&self) -> Rgb32FImage {
+        output::linear_image(self.width, self.height, |x, y| self.color(x, y))
+    }
+
+    /// Returns the number of samples taken through the pixel x, y.
+    #[must_use]
+    pub fn sample_count(&self, x: u32, y: u32) -> u32 {
+        self.pixels[self.index(x, y)].sample_count
+    }
+
+    const fn index(&self, x: u32, y: u32) -> usize {
+        y as usize * self.width as usize + x as usize
+    }
+}
+
+/// A part of the film which a single thread can add samples to, to be merged into the film later.
+#[derive(Debug, Default, Clone)]
+pub struct FilmTile {
+    x_range: Range<u32>,
+    y_range: Range<u32>,
+    pixels: Vec<FilmPixel>,
+}
+
+impl FilmTile {
+    /// Adds a sample taken through the pixel x, y at an offset in [-0.5, 0.5] from its center to
+    /// all pixels within reach of the filter.
+    #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn add_sample(
+        &mut self,
+        [x, y]: [u32; 2],
+        offset: [f64; 2],
+        radiance: Radiance,
+        filter: &dyn Filter,
+    ) {
+        let position = [
+            f64::from(x) + 0.5 + offset[0],
+            f64::from(y) + 0.5 + offset[1],
+        ];
+        let radius = filter.radius();
+        let start = position.map(|value| (value - 0.5 - radius).ceil().max(0.0) as u32);
+        let end = position.map(|value| (value - 0.5 + radius).floor().max(-1.0) as i64 + 1);
+        let x_range =
+            start[0].max(self.x_range.start)..(end[0].max(0) as u32).min(self.x_range.end);
+        let y_range =
+            start[1].max(self.y_range.start)..(end[1].max(0) as u32).min(self.y_range.end);
+
+        for pixel_y in y_range {
+            for pixel_x in x_range.clone() {
+                let weight = filter.evaluate(
+                    f64::from(pixel_x) + 0.5 - position[0],
+                    f64::from(pixel_y) + 0.5 - position[1],
+                );
+                if weight != 0.0 {
+                    let index = self.index(pixel_x, pixel_y);
+                    self.pixels[index].weighted_direct += weight * radiance.direct;
+                    self.pixels[index].weighted_indirect += weight * radiance.indirect;
+                    self.pixels[index].weight_sum += weight;
+                }
+            }
+        }
+
+        if self.x_range.contains(&x) && self.y_range.contains(&y) {
+            let index = self.index(x, y);
+            self.pixels[index].sample_count += 1;
+        }
+    }
+
+    /// Counts a sample taken through the pixel x, y which was discarded, so the sample count
+    /// includes every sample spent on the pixel.
+    pub fn add_discarded_sample(&mut self, [x, y]: [u32; 2]) {
+        if self.x_range.contains(&x) && self.y_range.contains(&y) {
+            let index = self.index(x, y);
+            self.pixels[index].sample_count += 1;
+        }
+    }
+
+    const fn index(&self, x: u32, y: u32) -> usize {
+        (y - self.y_range.start) as usize * (self.x_range.end - self.x_range.start) as usize
+            + (x - self.x_range.start) as usize
+    }
+}
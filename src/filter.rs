@@ -0,0 +1,16 @@
+use std::fmt::Debug;
+
+pub mod blackman_harris;
+pub mod box_filter;
+pub mod gaussian;
+pub mod mitchell;
+pub mod tent;
+
+/// Weights the contribution of a sample to the pixels around it, by its offset from their centers
+/// in pixels.
+pub trait Filter: Debug + Send + Sync {
+    /// Distance from the sample beyond which the weight is zero, in pixels
+    fn radius(&self) -> f64;
+
+    fn evaluate(&self, x: f64, y: f64) -> f64;
+}
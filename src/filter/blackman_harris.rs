@@ -0,0 +1,34 @@
+use std::f64::consts::PI;
+
+use super::Filter;
+
+/// The four-term Blackman-Harris window, a smooth bell with very low sidelobes.
+#[derive(Debug, Clone, Copy)]
+pub struct BlackmanHarris {
+    radius: f64,
+}
+
+impl BlackmanHarris {
+    #[must_use]
+    pub const fn new(radius: f64) -> Self {
+        Self { radius }
+    }
+
+    fn evaluate_1d(self, x: f64) -> f64 {
+        if x.abs() > self.radius {
+            return 0.0;
+        }
+        let t = 2.0 * PI * (x / self.radius + 1.0) / 2.0;
+        0.358_75 - 0.488_29 * t.cos() + 0.141_28 * (2.0 * t).cos() - 0.011_68 * (3.0 * t).cos()
+    }
+}
+
+impl Filter for BlackmanHarris {
+    fn radius(&self) -> f64 {
+        self.radius
+    }
+
+    fn evaluate(&self, x: f64, y: f64) -> f64 {
+        self.evaluate_1d(x) * self.evaluate_1d(y)
+    }
+}
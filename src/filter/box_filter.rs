@@ -0,0 +1,33 @@
+use super::Filter;
+
+/// Weights every sample within its radius equally. A radius of half a pixel averages the samples
+/// of each pixel.
+#[derive(Debug, Clone, Copy)]
+pub struct BoxFilter {
+    radius: f64,
+}
+
+impl BoxFilter {
+    #[must_use]
+    pub const fn new(radius: f64) -> Self {
+        Self { radius }
+    }
+}
+
+impl Default for BoxFilter {
+    fn default() -> Self {
+        Self::new(0.5)
+    }
+}
+
+impl Filter for BoxFilter {
+    fn radius(&self) -> f64 {
+        self.radius
+    }
+
+    fn evaluate(&self, x: f64, y: f64) -> f64 {
+        // Half open, so samples on the edge between pixels only count for one of them
+        let inside = |value: f64| -self.radius < value && value <= self.radius;
+        if inside(x) && inside(y) { 1.0 } else { 0.0 }
+    }
+}
@@ -0,0 +1,42 @@
+use super::Filter;
+
+/// Weights samples by a Gaussian, shifted down to reach zero at its radius.
+#[derive(Debug, Clone, Copy)]
+pub struct Gaussian {
+    radius: f64,
+
+    /// Standard deviation in pixels
+    sigma: f64,
+
+    /// Value of the Gaussian at the radius
+    edge: f64,
+}
+
+impl Gaussian {
+    #[must_use]
+    pub fn new(radius: f64, sigma: f64) -> Self {
+        Self {
+            radius,
+            sigma,
+            edge: gaussian(radius, sigma),
+        }
+    }
+
+    fn evaluate_1d(&self, x: f64) -> f64 {
+        (gaussian(x, self.sigma) - self.edge).max(0.0)
+    }
+}
+
+fn gaussian(x: f64, sigma: f64) -> f64 {
+    (-x * x / (2.0 * sigma * sigma)).exp()
+}
+
+impl Filter for Gaussian {
+    fn radius(&self) -> f64 {
+        self.radius
+    }
+
+    fn evaluate(&self, x: f64, y: f64) -> f64 {
+        self.evaluate_1d(x) * self.evaluate_1d(y)
+    }
+}
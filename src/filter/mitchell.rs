@@ -0,0 +1,52 @@
+use super::Filter;
+
+/// The Mitchell-Netravali cubic filter, trading blurring for ringing through its B and C
+/// parameters. B = C = 1/3 is the recommended balance.
+#[derive(Debug, Clone, Copy)]
+pub struct Mitchell {
+    radius: f64,
+    b: f64,
+    c: f64,
+}
+
+impl Mitchell {
+    #[must_use]
+    pub const fn new(radius: f64, b: f64, c: f64) -> Self {
+        Self { radius, b, c }
+    }
+
+    /// Evaluates the filter over [-2, 2].
+    fn evaluate_1d(&self, x: f64) -> f64 {
+        let (b, c) = (self.b, self.c);
+        let x = x.abs();
+        let value = if x <= 1.0 {
+            (12.0 - 9.0 * b - 6.0 * c) * x.powi(3)
+                + (-18.0 + 12.0 * b + 6.0 * c) * x * x
+                + (6.0 - 2.0 * b)
+        } else if x <= 2.0 {
+            (-b - 6.0 * c) * x.powi(3)
+                + (6.0 * b + 30.0 * c) * x * x
+                + (-12.0 * b - 48.0 * c) * x
+                + (8.0 * b + 24.0 * c)
+        } else {
+            0.0
+        };
+        value / 6.0
+    }
+}
+
+impl Default for Mitchell {
+    fn default() -> Self {
+        Self::new(2.0, 1.0 / 3.0, 1.0 / 3.0)
+    }
+}
+
+impl Filter for Mitchell {
+    fn radius(&self) -> f64 {
+        self.radius
+    }
+
+    fn evaluate(&self, x: f64, y: f64) -> f64 {
+        self.evaluate_1d(2.0 * x / self.radius) * self.evaluate_1d(2.0 * y / self.radius)
+    }
+}
@@ -0,0 +1,24 @@
+use super::Filter;
+
+/// Weights samples linearly decreasing to zero at its radius.
+#[derive(Debug, Clone, Copy)]
+pub struct Tent {
+    radius: f64,
+}
+
+impl Tent {
+    #[must_use]
+    pub const fn new(radius: f64) -> Self {
+        Self { radius }
+    }
+}
+
+impl Filter for Tent {
+    fn radius(&self) -> f64 {
+        self.radius
+    }
+
+    fn evaluate(&self, x: f64, y: f64) -> f64 {
+        (self.radius - x.abs()).max(0.0) * (self.radius - y.abs()).max(0.0)
+    }
+}
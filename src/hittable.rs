@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{fmt::Debug, sync::Arc};
 
 use crate::{
     interval::Interval,
@@ -7,15 +7,35 @@ use crate::{
     vec3::{Point3, Vec3},
 };
 
+pub mod alpha_mask;
+pub mod bump_map;
 pub mod list;
+pub mod normal_map;
 pub mod sphere;
 
 pub struct HitRecord {
     point: Point3,
+
+    /// Shading normal, facing against the ray
     normal: Vec3,
+
+    /// Normal of the actual surface, facing against the ray
+    geometric_normal: Vec3,
+
+    /// Direction of increasing u along the surface
+    tangent: Vec3,
+
+    /// Direction of increasing v along the surface, completing the frame with the outward normal
+    bitangent: Vec3,
+
+    u: f64,
+    v: f64,
     material: Arc<dyn Material>,
     time: f64,
     front_face: bool,
+
+    /// Index plus one of the object in the outermost list containing it, or zero outside lists
+    object_id: u32,
 }
 
 impl HitRecord {
@@ -32,13 +52,46 @@ impl HitRecord {
         } else {
             -outward_normal
         };
+        let tangent = outward_normal.perpendicular();
         Self {
             point,
             normal,
+            geometric_normal: normal,
+            tangent,
+            bitangent: outward_normal.cross(&tangent),
+            u: 0.0,
+            v: 0.0,
             material,
             time,
             front_face,
+            object_id: 0,
+        }
+    }
+
+    /// Sets the surface coordinates of the hit point and the direction in which u increases.
+    #[must_use]
+    pub fn with_surface_coordinates(mut self, u: f64, v: f64, tangent: Vec3) -> Self {
+        self.u = u;
+        self.v = v;
+
+        // Project the tangent on the surface, falling back to any direction at singularities
+        let outward_normal = self.outward_normal();
+        let tangent = tangent - tangent.dot(&outward_normal) * outward_normal;
+        if !tangent.near_zero() {
+            self.tangent = tangent.unit_vector();
+            self.bitangent = outward_normal.cross(&self.tangent);
         }
+        self
+    }
+
+    /// Replaces the shading normal, given as facing out of the surface.
+    pub fn set_shading_normal(&mut self, outward_normal: Vec3) {
+        let outward_normal = outward_normal.unit_vector();
+        self.normal = if self.front_face {
+            outward_normal
+        } else {
+            -outward_normal
+        };
     }
 
     #[must_use]
@@ -51,6 +104,41 @@ impl HitRecord {
         self.normal
     }
 
+    #[must_use]
+    pub const fn geometric_normal(&self) -> Vec3 {
+        self.geometric_normal
+    }
+
+    /// The geometric normal facing out of the surface, regardless of the side that was hit
+    #[must_use]
+    pub fn outward_normal(&self) -> Vec3 {
+        if self.front_face {
+            self.geometric_normal
+        } else {
+            -self.geometric_normal
+        }
+    }
+
+    #[must_use]
+    pub const fn tangent(&self) -> Vec3 {
+        self.tangent
+    }
+
+    #[must_use]
+    pub const fn bitangent(&self) -> Vec3 {
+        self.bitangent
+    }
+
+    #[must_use]
+    pub const fn u(&self) -> f64 {
+        self.u
+    }
+
+    #[must_use]
+    pub const fn v(&self) -> f64 {
+        self.v
+    }
+
     #[must_use]
     pub const fn time(&self) -> f64 {
         self.time
@@ -65,8 +153,20 @@ impl HitRecord {
     pub const fn front_face(&self) -> bool {
         self.front_face
     }
+
+    #[must_use]
+    pub const fn object_id(&self) -> u32 {
+        self.object_id
+    }
+
+    pub const fn set_object_id(&mut self, object_id: u32) {
+        self.object_id = object_id;
+    }
 }
 
-pub trait Hittable {
+pub trait Hittable: Debug {
     fn hit(&self, ray: &Ray, ray_time: Interval) -> Option<HitRecord>;
+
+    /// Calls `visit` with the material of every object, in the order the objects were added.
+    fn visit_materials(&self, visit: &mut dyn FnMut(&Arc<dyn Material>));
 }
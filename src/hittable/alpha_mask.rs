@@ -0,0 +1,66 @@
+use std::sync::Arc;
+
+use crate::{
+    interval::Interval,
+    material::Material,
+    ray::Ray,
+    sampler::{hash, hash_to_unit},
+    texture::Texture,
+};
+
+use super::{HitRecord, Hittable};
+
+/// Cuts an object out by an opacity mask, read as the average of the texture channels. Rays pass
+/// through where the opacity is 0, and through a matching fraction of the hits where it lies
+/// between 0 and 1.
+#[derive(Debug)]
+pub struct AlphaMask {
+    object: Box<dyn Hittable + Sync>,
+    alpha: Arc<dyn Texture>,
+}
+
+impl AlphaMask {
+    #[must_use]
+    pub const fn new(object: Box<dyn Hittable + Sync>, alpha: Arc<dyn Texture>) -> Self {
+        Self { object, alpha }
+    }
+}
+
+impl Hittable for AlphaMask {
+    fn hit(&self, ray: &Ray, mut ray_time: Interval) -> Option<HitRecord> {
+        loop {
+            let record = self.object.hit(ray, ray_time)?;
+            let value = self.alpha.value(record.u(), record.v(), record.point());
+            let alpha = (value.x() + value.y() + value.z()) / 3.0;
+            if alpha >= 1.0 || (alpha > 0.0 && alpha > hash_ray(ray, record.time())) {
+                return Some(record);
+            }
+
+            // Continue behind the rejected hit, so the object reports at most one hit per query.
+            ray_time = Interval::new(record.time(), ray_time.max());
+        }
+    }
+
+    fn visit_materials(&self, visit: &mut dyn FnMut(&Arc<dyn Material>)) {
+        self.object.visit_materials(visit);
+    }
+}
+
+/// Returns a number in [0, 1) derived from the ray and hit time. Hashing instead of drawing a
+/// random number makes the same ray always take the same decision for the same hit.
+fn hash_ray(ray: &Ray, time: f64) -> f64 {
+    let origin = ray.origin();
+    let direction = ray.direction();
+    hash_to_unit(hash(
+        [
+            origin.x(),
+            origin.y(),
+            origin.z(),
+            direction.x(),
+            direction.y(),
+            direction.z(),
+            time,
+        ]
+        .map(f64::to_bits),
+    ))
+}
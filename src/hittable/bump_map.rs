@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use crate::{interval::Interval, material::Material, ray::Ray, texture::Texture};
+
+use super::{HitRecord, Hittable};
+
+/// Step in surface coordinates used to estimate the slope of the height map
+const DELTA: f64 = 1.0 / 1024.0;
+
+/// Perturbs the shading normals of an object by the slope of a scalar height map, read as the
+/// average of the texture channels over the surface coordinates.
+#[derive(Debug)]
+pub struct BumpMap {
+    object: Box<dyn Hittable + Sync>,
+    height: Arc<dyn Texture>,
+
+    /// Height change for a texture value change of 1
+    scale: f64,
+}
+
+impl BumpMap {
+    #[must_use]
+    pub const fn new(
+        object: Box<dyn Hittable + Sync>,
+        height: Arc<dyn Texture>,
+        scale: f64,
+    ) -> Self {
+        Self {
+            object,
+            height,
+            scale,
+        }
+    }
+
+    fn height(&self, record: &HitRecord, u: f64, v: f64) -> f64 {
+        let value = self.height.value(u, v, record.point());
+        self.scale * (value.x() + value.y() + value.z()) / 3.0
+    }
+}
+
+impl Hittable for BumpMap {
+    fn hit(&self, ray: &Ray, ray_time: Interval) -> Option<HitRecord> {
+        let mut record = self.object.hit(ray, ray_time)?;
+        let (u, v) = (record.u(), record.v());
+        let height = self.height(&record, u, v);
+        let slope_u = (self.height(&record, u + DELTA, v) - height) / DELTA;
+        let slope_v = (self.height(&record, u, v + DELTA) - height) / DELTA;
+        let normal =
+            record.outward_normal() - slope_u * record.tangent() - slope_v * record.bitangent();
+        record.set_shading_normal(normal);
+        Some(record)
+    }
+
+    fn visit_materials(&self, visit: &mut dyn FnMut(&Arc<dyn Material>)) {
+        self.object.visit_materials(visit);
+    }
+}
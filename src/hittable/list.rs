@@ -1,8 +1,10 @@
-use crate::{interval::Interval, ray::Ray};
+use std::sync::Arc;
+
+use crate::{interval::Interval, material::Material, ray::Ray};
 
 use super::{HitRecord, Hittable};
 
-#[derive(Default)]
+#[derive(Debug, Default)]
 pub struct HittableList {
     objects: Vec<Box<dyn Hittable + Sync>>,
 }
@@ -24,9 +26,19 @@ impl HittableList {
 
 impl Hittable for HittableList {
     fn hit(&self, ray: &Ray, ray_time: Interval) -> Option<HitRecord> {
-        self.objects
+        let (index, mut record) = self
+            .objects
             .iter()
-            .filter_map(|object| object.hit(ray, ray_time))
-            .min_by(|left, right| left.time.total_cmp(&right.time))
+            .enumerate()
+            .filter_map(|(index, object)| Some((index, object.hit(ray, ray_time)?)))
+            .min_by(|(_, left), (_, right)| left.time.total_cmp(&right.time))?;
+        record.set_object_id(u32::try_from(index + 1).unwrap_or(u32::MAX));
+        Some(record)
+    }
+
+    fn visit_materials(&self, visit: &mut dyn FnMut(&Arc<dyn Material>)) {
+        for object in &self.objects {
+            object.visit_materials(visit);
+        }
     }
 }
@@ -0,0 +1,38 @@
+use std::sync::Arc;
+
+use crate::{interval::Interval, material::Material, ray::Ray, texture::Texture};
+
+use super::{HitRecord, Hittable};
+
+/// Perturbs the shading normals of an object by a tangent-space normal map, where the red, green
+/// and blue channels map the tangent, bitangent and normal direction from [0, 1] to [-1, 1].
+#[derive(Debug)]
+pub struct NormalMap {
+    object: Box<dyn Hittable + Sync>,
+    map: Arc<dyn Texture>,
+}
+
+impl NormalMap {
+    #[must_use]
+    pub const fn new(object: Box<dyn Hittable + Sync>, map: Arc<dyn Texture>) -> Self {
+        Self { object, map }
+    }
+}
+
+impl Hittable for NormalMap {
+    fn hit(&self, ray: &Ray, ray_time: Interval) -> Option<HitRecord> {
+        let mut record = self.object.hit(ray, ray_time)?;
+        let value = self.map.value(record.u(), record.v(), record.point());
+        let normal = (2.0 * value.x() - 1.0) * record.tangent()
+            + (2.0 * value.y() - 1.0) * record.bitangent()
+            + (2.0 * value.z() - 1.0) * record.outward_normal();
+        if !normal.near_zero() {
+            record.set_shading_normal(normal);
+        }
+        Some(record)
+    }
+
+    fn visit_materials(&self, visit: &mut dyn FnMut(&Arc<dyn Material>)) {
+        self.object.visit_materials(visit);
+    }
+}
@@ -1,6 +1,11 @@
-use std::sync::Arc;
+use std::{f64::consts::PI, sync::Arc};
 
-use crate::{interval::Interval, material::Material, ray::Ray, vec3::Point3};
+use crate::{
+    interval::Interval,
+    material::Material,
+    ray::Ray,
+    vec3::{Point3, Vec3},
+};
 
 use super::{HitRecord, Hittable};
 
@@ -19,6 +24,14 @@ impl Sphere {
             material,
         }
     }
+
+    /// Returns the surface coordinates of a point on the unit sphere, with u the angle around the
+    /// Y axis from X=-1 and v the angle from Y=-1 to Y=+1, both mapped to [0, 1].
+    fn uv(point: Point3) -> (f64, f64) {
+        let theta = (-point.y()).acos();
+        let phi = (-point.z()).atan2(point.x()) + PI;
+        (phi / (2.0 * PI), theta / PI)
+    }
 }
 
 impl Hittable for Sphere {
@@ -47,12 +60,15 @@ impl Hittable for Sphere {
         let time = root;
         let point = ray.at(time);
         let outward_normal = (point - self.center) / self.radius;
-        Some(HitRecord::new(
-            ray,
-            outward_normal,
-            self.material.clone(),
-            point,
-            time,
-        ))
+        let (surface_u, surface_v) = Self::uv(outward_normal);
+        let tangent = Vec3::new([outward_normal.z(), 0.0, -outward_normal.x()]);
+        Some(
+            HitRecord::new(ray, outward_normal, self.material.clone(), point, time)
+                .with_surface_coordinates(surface_u, surface_v, tangent),
+        )
+    }
+
+    fn visit_materials(&self, visit: &mut dyn FnMut(&Arc<dyn Material>)) {
+        visit(&self.material);
     }
 }
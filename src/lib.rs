@@ -0,0 +1,19 @@
+#![warn(clippy::pedantic)]
+
+pub mod camera;
+pub mod denoise;
+pub mod display;
+pub mod film;
+pub mod filter;
+pub mod hittable;
+pub mod interval;
+pub mod light;
+pub mod material;
+pub mod output;
+pub mod ray;
+pub mod sampler;
+pub mod scene;
+pub mod sky;
+pub mod spectrum;
+pub mod texture;
+pub mod vec3;
@@ -0,0 +1,30 @@
+use std::fmt::Debug;
+
+use crate::{
+    sampler::Sampler,
+    vec3::{Color, Point3, Vec3},
+};
+
+pub mod directional;
+pub mod point;
+pub mod spot;
+
+/// Light arriving at a point from a light source
+#[derive(Debug, Clone, Copy)]
+pub struct LightSample {
+    /// Unit direction from the point towards the light
+    pub direction: Vec3,
+
+    /// Distance to the light, infinite for lights without a position
+    pub distance: f64,
+
+    /// Irradiance on a surface facing the light
+    pub irradiance: Color,
+}
+
+/// A light source without geometry, which rays can't hit and which therefore has to be sampled
+/// explicitly.
+pub trait Light: Debug + Sync + Send {
+    /// Samples the light arriving at a point, if any.
+    fn sample(&self, point: Point3, sampler: &mut dyn Sampler) -> Option<LightSample>;
+}
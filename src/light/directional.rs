@@ -0,0 +1,54 @@
+use std::f64::consts::PI;
+
+use crate::{
+    sampler::Sampler,
+    vec3::{Color, Point3, Vec3},
+};
+
+use super::{Light, LightSample};
+
+/// A distant light, like the sun, arriving from a small cone of directions which softens the
+/// shadows it casts.
+#[derive(Debug, Clone, Copy)]
+pub struct DirectionalLight {
+    /// Unit direction towards the light
+    direction: Vec3,
+
+    /// Irradiance on a surface facing the light
+    irradiance: Color,
+
+    /// Cosine of the angular radius of the light
+    cos_radius: f64,
+}
+
+impl DirectionalLight {
+    /// Creates a light arriving from the given direction, covering an angular diameter in degrees.
+    /// The sun has an angular diameter of about 0.53 degrees.
+    #[must_use]
+    pub fn new(direction: Vec3, irradiance: Color, angular_diameter: f64) -> Self {
+        Self {
+            direction: direction.unit_vector(),
+            irradiance,
+            cos_radius: (angular_diameter / 2.0).to_radians().cos(),
+        }
+    }
+}
+
+impl Light for DirectionalLight {
+    fn sample(&self, _point: Point3, sampler: &mut dyn Sampler) -> Option<LightSample> {
+        // Sample a direction uniformly within the cone around the light direction
+        let [u1, u2] = sampler.get_2d();
+        let cos_theta = 1.0 - u1 * (1.0 - self.cos_radius);
+        let sin_theta = (1.0 - cos_theta * cos_theta).sqrt();
+        let phi = 2.0 * PI * u2;
+        let tangent = self.direction.perpendicular();
+        let bitangent = self.direction.cross(&tangent);
+        let direction =
+            cos_theta * self.direction + sin_theta * (phi.cos() * tangent + phi.sin() * bitangent);
+        Some(LightSample {
+            direction,
+            distance: f64::INFINITY,
+            irradiance: self.irradiance,
+        })
+    }
+}
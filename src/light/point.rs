@@ -0,0 +1,41 @@
+use crate::{
+    sampler::Sampler,
+    vec3::{Color, Point3},
+};
+
+use super::{Light, LightSample};
+
+/// A light emitting equally in all directions from a single point.
+#[derive(Debug, Clone, Copy)]
+pub struct PointLight {
+    position: Point3,
+
+    /// Radiant intensity, the power per steradian
+    intensity: Color,
+}
+
+impl PointLight {
+    #[must_use]
+    pub const fn new(position: Point3, intensity: Color) -> Self {
+        Self {
+            position,
+            intensity,
+        }
+    }
+}
+
+impl Light for PointLight {
+    fn sample(&self, point: Point3, _sampler: &mut dyn Sampler) -> Option<LightSample> {
+        let offset = self.position - point;
+        let distance_squared = offset.length_squared();
+        if distance_squared == 0.0 {
+            return None;
+        }
+        let distance = distance_squared.sqrt();
+        Some(LightSample {
+            direction: offset / distance,
+            distance,
+            irradiance: self.intensity / distance_squared,
+        })
+    }
+}
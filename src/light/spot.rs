@@ -0,0 +1,75 @@
+use crate::{
+    sampler::Sampler,
+    vec3::{Color, Point3, Vec3},
+};
+
+use super::{Light, LightSample};
+
+/// A point light emitting in a cone, fading out smoothly towards its edge.
+#[derive(Debug, Clone, Copy)]
+pub struct SpotLight {
+    position: Point3,
+
+    /// Unit direction of the cone axis
+    direction: Vec3,
+
+    /// Radiant intensity along the axis, the power per steradian
+    intensity: Color,
+
+    /// Cosine of the angle between the axis and the edge of the cone
+    cos_cone: f64,
+
+    /// Cosine of the angle between the axis and where the light starts fading
+    cos_falloff_start: f64,
+}
+
+impl SpotLight {
+    /// Creates a spot light with a cone angle and falloff start angle in degrees, measured from the
+    /// axis.
+    #[must_use]
+    pub fn new(
+        position: Point3,
+        direction: Vec3,
+        intensity: Color,
+        cone_angle: f64,
+        falloff_start: f64,
+    ) -> Self {
+        Self {
+            position,
+            direction: direction.unit_vector(),
+            intensity,
+            cos_cone: cone_angle.to_radians().cos(),
+            cos_falloff_start: falloff_start.min(cone_angle).to_radians().cos(),
+        }
+    }
+
+    /// Intensity scale along a direction from the light with the given cosine to the axis
+    fn falloff(&self, cos_theta: f64) -> f64 {
+        if cos_theta >= self.cos_falloff_start {
+            return 1.0;
+        }
+        if cos_theta <= self.cos_cone {
+            return 0.0;
+        }
+        let t = (cos_theta - self.cos_cone) / (self.cos_falloff_start - self.cos_cone);
+        t * t * (3.0 - 2.0 * t)
+    }
+}
+
+impl Light for SpotLight {
+    fn sample(&self, point: Point3, _sampler: &mut dyn Sampler) -> Option<LightSample> {
+        let offset = self.position - point;
+        let distance_squared = offset.length_squared();
+        if distance_squared == 0.0 {
+            return None;
+        }
+        let distance = distance_squared.sqrt();
+        let direction = offset / distance;
+        let falloff = self.falloff(self.direction.dot(&-direction));
+        (falloff > 0.0).then(|| LightSample {
+            direction,
+            distance,
+            irradiance: self.intensity * falloff / distance_squared,
+        })
+    }
+}
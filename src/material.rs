@@ -1,11 +1,49 @@
 use std::fmt::Debug;
 
-use crate::{hittable::HitRecord, ray::Ray, vec3::Color};
+use crate::{
+    hittable::HitRecord,
+    ray::Ray,
+    sampler::Sampler,
+    vec3::{Color, Vec3},
+};
 
+pub mod anisotropic_metal;
+pub mod coated;
 pub mod dielectric;
+pub mod emitter;
+pub mod fresnel;
 pub mod lambertian;
 pub mod metal;
+pub mod mix;
+pub mod oren_nayar;
+pub mod subsurface;
+pub mod thin_dielectric;
+pub mod thin_film;
 
 pub trait Material: Debug + Sync + Send {
-    fn scatter(&self, ray_in: &Ray, record: &HitRecord) -> Option<(Color, Ray)>;
+    fn scatter(
+        &self,
+        ray_in: &Ray,
+        record: &HitRecord,
+        sampler: &mut dyn Sampler,
+    ) -> Option<(Color, Ray)>;
+
+    fn emitted(&self, _ray_in: &Ray, _record: &HitRecord) -> Color {
+        Color::default()
+    }
+
+    /// Returns the fraction of the light arriving from a unit direction that is scattered along
+    /// the incoming ray, multiplied by the cosine to the normal. This is the only way materials are
+    /// lit by the point, spot and directional lights, which scattered rays can never hit.
+    /// Materials which only scatter into discrete directions, like glass and mirrors, keep the
+    /// default of black and are only lit by what their scattered rays hit.
+    fn evaluate(&self, _ray_in: &Ray, _record: &HitRecord, _direction: Vec3) -> Color {
+        Color::default()
+    }
+
+    /// Returns the color of the surface, as used by feature buffers guiding a denoiser. Materials
+    /// without a color of their own, like glass, keep the default of white.
+    fn albedo(&self, _record: &HitRecord) -> Color {
+        Color::new([1.0; 3])
+    }
 }
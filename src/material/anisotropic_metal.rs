@@ -0,0 +1,185 @@
+use std::f64::consts::PI;
+
+use crate::{
+    hittable::HitRecord,
+    ray::Ray,
+    sampler::Sampler,
+    vec3::{Color, Vec3},
+};
+
+use super::Material;
+
+/// A metal with different roughness along and across a tangent direction, like brushed aluminium,
+/// reflecting by an anisotropic GGX microfacet distribution.
+#[derive(Debug, Clone, Copy)]
+pub struct AnisotropicMetal {
+    /// Reflectance at normal incidence
+    albedo: Color,
+
+    /// Microfacet slope deviation along the tangent
+    alpha_tangent: f64,
+
+    /// Microfacet slope deviation along the bitangent
+    alpha_bitangent: f64,
+
+    /// Direction of the tangent, or `None` to use the direction of increasing u on the surface
+    tangent: Option<Vec3>,
+}
+
+impl AnisotropicMetal {
+    /// Creates a metal with a roughness in [0, 1] along and across the direction of increasing u
+    /// on the surface.
+    #[must_use]
+    pub fn new(albedo: Color, roughness_tangent: f64, roughness_bitangent: f64) -> Self {
+        Self {
+            albedo,
+            alpha_tangent: roughness_to_alpha(roughness_tangent),
+            alpha_bitangent: roughness_to_alpha(roughness_bitangent),
+            tangent: None,
+        }
+    }
+
+    /// Uses a fixed world space tangent direction, projected on the surface, instead of the one
+    /// derived from the surface coordinates.
+    #[must_use]
+    pub const fn with_tangent(mut self, tangent: Vec3) -> Self {
+        self.tangent = Some(tangent);
+        self
+    }
+
+    /// Local shading frame of tangent, bitangent and normal, with the tangent projected on the
+    /// plane of the shading normal
+    fn frame(&self, record: &HitRecord) -> [Vec3; 3] {
+        let normal = record.normal();
+        let project = |tangent: Vec3| tangent - tangent.dot(&normal) * normal;
+        let mut tangent = project(self.tangent.unwrap_or_else(|| record.tangent()));
+        if tangent.near_zero() {
+            tangent = project(record.tangent());
+        }
+        let tangent = if tangent.near_zero() {
+            normal.perpendicular()
+        } else {
+            tangent.unit_vector()
+        };
+        [tangent, normal.cross(&tangent), normal]
+    }
+
+    /// Schlick's approximation with the albedo as reflectance at normal incidence
+    fn fresnel(&self, cosine: f64) -> Color {
+        self.albedo + (Color::new([1.0; 3]) - self.albedo) * (1.0 - cosine.clamp(0.0, 1.0)).powi(5)
+    }
+
+    /// Density of microfacet normals in the local frame
+    fn distribution(&self, normal: Vec3) -> f64 {
+        let scaled = (normal.x() / self.alpha_tangent).powi(2)
+            + (normal.y() / self.alpha_bitangent).powi(2)
+            + normal.z() * normal.z();
+        1.0 / (PI * self.alpha_tangent * self.alpha_bitangent * scaled * scaled)
+    }
+
+    /// Smith masking term of the distribution for a direction in the local frame
+    fn lambda(&self, direction: Vec3) -> f64 {
+        let tan2 = ((self.alpha_tangent * direction.x()).powi(2)
+            + (self.alpha_bitangent * direction.y()).powi(2))
+            / (direction.z() * direction.z());
+        0.5 * ((1.0 + tan2).sqrt() - 1.0)
+    }
+
+    /// Samples a microfacet normal visible from the given direction in the local frame, using two
+    /// uniform numbers in [0, 1).
+    fn sample_visible_normal(&self, outgoing: Vec3, [u1, u2]: [f64; 2]) -> Vec3 {
+        // Transform the view direction to the hemisphere configuration
+        let view = Vec3::new([
+            self.alpha_tangent * outgoing.x(),
+            self.alpha_bitangent * outgoing.y(),
+            outgoing.z(),
+        ])
+        .unit_vector();
+
+        // Orthonormal basis around the view direction
+        let length_squared = view.x() * view.x() + view.y() * view.y();
+        let basis_x = if length_squared > 0.0 {
+            Vec3::new([-view.y(), view.x(), 0.0]) / length_squared.sqrt()
+        } else {
+            Vec3::new([1.0, 0.0, 0.0])
+        };
+        let basis_y = view.cross(&basis_x);
+
+        // Sample a point on the projected area of the visible hemisphere
+        let radius = u1.sqrt();
+        let phi = 2.0 * PI * u2;
+        let t1 = radius * phi.cos();
+        let s = 0.5 * (1.0 + view.z());
+        let t2 = (1.0 - s) * (1.0 - t1 * t1).sqrt() + s * radius * phi.sin();
+        let normal = t1 * basis_x + t2 * basis_y + (1.0 - t1 * t1 - t2 * t2).max(0.0).sqrt() * view;
+
+        // Transform the normal back to the ellipsoid configuration
+        Vec3::new([
+            self.alpha_tangent * normal.x(),
+            self.alpha_bitangent * normal.y(),
+            normal.z().max(0.0),
+        ])
+        .unit_vector()
+    }
+}
+
+fn roughness_to_alpha(roughness: f64) -> f64 {
+    roughness.clamp(0.0, 1.0).powi(2).max(1e-4)
+}
+
+impl Material for AnisotropicMetal {
+    fn scatter(
+        &self,
+        ray_in: &Ray,
+        record: &HitRecord,
+        sampler: &mut dyn Sampler,
+    ) -> Option<(Color, Ray)> {
+        let frame = self.frame(record);
+        let outgoing = to_local(&frame, -ray_in.direction().unit_vector());
+        if outgoing.z() <= 0.0 {
+            return None;
+        }
+        let microfacet = self.sample_visible_normal(outgoing, sampler.get_2d());
+        let cos_theta = outgoing.dot(&microfacet);
+        let incoming = 2.0 * cos_theta * microfacet - outgoing;
+        if incoming.z() <= 0.0 {
+            return None;
+        }
+
+        // Sampling visible normals leaves the Fresnel term and the ratio of the masking-shadowing
+        // over the masking term
+        let lambda_out = self.lambda(outgoing);
+        let masking_shadowing = (1.0 + lambda_out) / (1.0 + lambda_out + self.lambda(incoming));
+
+        let [tangent, bitangent, normal] = frame;
+        let direction = incoming.x() * tangent + incoming.y() * bitangent + incoming.z() * normal;
+        let scattered = Ray::new(record.point(), direction);
+        (scattered.direction().dot(&record.geometric_normal()) > 0.0)
+            .then_some((self.fresnel(cos_theta) * masking_shadowing, scattered))
+    }
+
+    fn evaluate(&self, ray_in: &Ray, record: &HitRecord, direction: Vec3) -> Color {
+        if record.geometric_normal().dot(&direction) <= 0.0 {
+            return Color::default();
+        }
+        let frame = self.frame(record);
+        let outgoing = to_local(&frame, -ray_in.direction().unit_vector());
+        let incoming = to_local(&frame, direction);
+        if outgoing.z() <= 0.0 || incoming.z() <= 0.0 {
+            return Color::default();
+        }
+        let microfacet = (outgoing + incoming).unit_vector();
+        let masking_shadowing = 1.0 / (1.0 + self.lambda(outgoing) + self.lambda(incoming));
+        self.fresnel(outgoing.dot(&microfacet))
+            * (self.distribution(microfacet) * masking_shadowing / (4.0 * outgoing.z()))
+    }
+
+    fn albedo(&self, _record: &HitRecord) -> Color {
+        self.albedo
+    }
+}
+
+/// Expresses a direction in a local frame of tangent, bitangent and normal.
+fn to_local(frame: &[Vec3; 3], direction: Vec3) -> Vec3 {
+    Vec3::new(frame.map(|axis| axis.dot(&direction)))
+}
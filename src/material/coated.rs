@@ -0,0 +1,83 @@
+use std::sync::Arc;
+
+use crate::{
+    hittable::HitRecord,
+    ray::Ray,
+    sampler::Sampler,
+    vec3::{Color, Vec3},
+};
+
+use super::{Material, fresnel};
+
+/// A smooth dielectric clearcoat layered over another material, like car paint or varnished wood.
+#[derive(Debug, Clone)]
+pub struct Coated {
+    base: Arc<dyn Material>,
+    refraction_index: f64,
+}
+
+impl Coated {
+    #[must_use]
+    pub const fn new(base: Arc<dyn Material>, refraction_index: f64) -> Self {
+        Self {
+            base,
+            refraction_index,
+        }
+    }
+
+    /// Fraction of the light passing through the coat along a unit direction
+    fn transmittance(&self, record: &HitRecord, direction: Vec3) -> f64 {
+        let cosine = record.normal().dot(&direction).abs();
+        1.0 - fresnel::dielectric(cosine, 1.0 / self.refraction_index)
+    }
+}
+
+impl Material for Coated {
+    fn scatter(
+        &self,
+        ray_in: &Ray,
+        record: &HitRecord,
+        sampler: &mut dyn Sampler,
+    ) -> Option<(Color, Ray)> {
+        if !record.front_face() {
+            return self.base.scatter(ray_in, record, sampler);
+        }
+        let unit_direction = ray_in.direction().unit_vector();
+        let cos_theta = record.normal().dot(&-unit_direction).min(1.0);
+
+        // The coat reflects a Fresnel-weighted fraction of the light...
+        let reflectance = fresnel::dielectric(cos_theta, 1.0 / self.refraction_index);
+        if reflectance > sampler.get_1d() {
+            let direction = unit_direction.reflect(&record.normal());
+            return Some((Color::new([1.0; 3]), Ray::new(record.point(), direction)));
+        }
+
+        // ...and transmits the rest to the base, of which only the part transmitted by the coat on
+        // the way out leaves the surface.
+        let (attenuation, scattered) = self.base.scatter(ray_in, record, sampler)?;
+        let transmittance = self.transmittance(record, scattered.direction().unit_vector());
+        Some((attenuation * transmittance, scattered))
+    }
+
+    fn emitted(&self, ray_in: &Ray, record: &HitRecord) -> Color {
+        let emitted = self.base.emitted(ray_in, record);
+        if !record.front_face() {
+            return emitted;
+        }
+        emitted * self.transmittance(record, -ray_in.direction().unit_vector())
+    }
+
+    fn evaluate(&self, ray_in: &Ray, record: &HitRecord, direction: Vec3) -> Color {
+        let evaluated = self.base.evaluate(ray_in, record, direction);
+        if !record.front_face() {
+            return evaluated;
+        }
+        evaluated
+            * self.transmittance(record, -ray_in.direction().unit_vector())
+            * self.transmittance(record, direction)
+    }
+
+    fn albedo(&self, record: &HitRecord) -> Color {
+        self.base.albedo(record)
+    }
+}
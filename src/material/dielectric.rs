@@ -1,45 +1,100 @@
-use rand::random;
+use crate::{
+    hittable::HitRecord,
+    ray::Ray,
+    sampler::Sampler,
+    vec3::{Color, Vec3},
+};
 
-use crate::{hittable::HitRecord, ray::Ray, vec3::Color};
-
-use super::Material;
+use super::{Material, fresnel, thin_film::ThinFilm};
 
 #[derive(Debug, Clone, Copy)]
 pub struct Dielectric {
     refraction_index: f64,
+    coating: Option<ThinFilm>,
 }
 
 impl Dielectric {
     #[must_use]
     pub const fn new(refraction_index: f64) -> Self {
-        Self { refraction_index }
+        Self {
+            refraction_index,
+            coating: None,
+        }
+    }
+
+    /// Covers the outside of the dielectric with a thin film.
+    #[must_use]
+    pub const fn with_coating(mut self, coating: ThinFilm) -> Self {
+        self.coating = Some(coating);
+        self
     }
 
-    fn reflectance(cosine: f64, refraction_index: f64) -> f64 {
-        let mut r0 = (1.0 - refraction_index) / (1.0 + refraction_index);
-        r0 *= r0;
-        r0 + (1.0 - r0) * (1.0 - cosine).powi(5)
+    /// Reflects or refracts a unit direction at a smooth interface with a normal facing against it,
+    /// randomly in proportion to the exact Fresnel reflectance using a uniform number in [0, 1).
+    /// `refraction_index` is the ratio of the index on the side of the direction over the other.
+    pub(super) fn reflect_or_refract(
+        unit_direction: Vec3,
+        normal: Vec3,
+        refraction_index: f64,
+        u: f64,
+    ) -> Vec3 {
+        let cos_theta = normal.dot(&-unit_direction).min(1.0);
+        // Total internal reflection has a reflectance of 1, so it always reflects
+        if fresnel::dielectric(cos_theta, refraction_index) > u {
+            unit_direction.reflect(&normal)
+        } else {
+            unit_direction.refract(&normal, refraction_index)
+        }
     }
 }
 
 impl Material for Dielectric {
-    fn scatter(&self, ray_in: &Ray, record: &HitRecord) -> Option<(Color, Ray)> {
-        let attenuation = Color::new([1.0; 3]);
+    fn scatter(
+        &self,
+        ray_in: &Ray,
+        record: &HitRecord,
+        sampler: &mut dyn Sampler,
+    ) -> Option<(Color, Ray)> {
         let refraction_index = if record.front_face() {
             1.0 / self.refraction_index
         } else {
             self.refraction_index
         };
         let unit_direction = ray_in.direction().unit_vector();
-        let cos_theta = record.normal().dot(&-unit_direction).min(1.0);
-        let sin_theta = (1.0 - cos_theta * cos_theta).sqrt();
-        let direction = if refraction_index * sin_theta > 1.0
-            || Self::reflectance(cos_theta, refraction_index) > random()
-        {
-            unit_direction.reflect(&record.normal())
-        } else {
-            unit_direction.refract(&record.normal(), refraction_index)
-        };
+
+        if let (Some(coating), true) = (self.coating, record.front_face()) {
+            let cos_theta = record.normal().dot(&-unit_direction).min(1.0);
+            let sin_theta = (1.0 - cos_theta * cos_theta).sqrt();
+            let cannot_refract = refraction_index * sin_theta > 1.0;
+
+            // The film reflects each channel differently, so choose between reflection and
+            // refraction by the average reflectance and weight the chosen path per channel.
+            let reflectance = coating.reflectance(cos_theta, 1.0, self.refraction_index);
+            let reflect_probability = (reflectance.x() + reflectance.y() + reflectance.z()) / 3.0;
+            return Some(
+                if cannot_refract || reflect_probability > sampler.get_1d() {
+                    let direction = unit_direction.reflect(&record.normal());
+                    (
+                        reflectance / reflect_probability,
+                        Ray::new(record.point(), direction),
+                    )
+                } else {
+                    let direction = unit_direction.refract(&record.normal(), refraction_index);
+                    (
+                        (Color::new([1.0; 3]) - reflectance) / (1.0 - reflect_probability),
+                        Ray::new(record.point(), direction),
+                    )
+                },
+            );
+        }
+
+        let attenuation = Color::new([1.0; 3]);
+        let direction = Self::reflect_or_refract(
+            unit_direction,
+            record.normal(),
+            refraction_index,
+            sampler.get_1d(),
+        );
         let scattered = Ray::new(record.point(), direction);
         Some((attenuation, scattered))
     }
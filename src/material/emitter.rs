@@ -0,0 +1,67 @@
+use std::f64::consts::PI;
+
+use crate::{hittable::HitRecord, ray::Ray, sampler::Sampler, spectrum, vec3::Color};
+
+use super::Material;
+
+/// A surface emitting light evenly in all directions from its front face, without reflecting any.
+/// The emitted color is linear sRGB with the luminance in candela per square meter.
+#[derive(Debug, Clone, Copy)]
+pub struct Emitter {
+    radiance: Color,
+}
+
+impl Emitter {
+    #[must_use]
+    pub const fn new(radiance: Color) -> Self {
+        Self { radiance }
+    }
+
+    /// Emits the color of a black body at a temperature in Kelvin, with the given luminance in
+    /// candela per square meter.
+    #[must_use]
+    pub fn blackbody(temperature: f64, luminance: f64) -> Self {
+        Self::new(spectrum::blackbody_color(temperature) * luminance)
+    }
+
+    /// Emits a spectral radiance, in watts per steradian per square meter per nanometer as a
+    /// function of the wavelength in nanometers.
+    #[must_use]
+    pub fn from_spectrum(spectral_radiance: impl Fn(f64) -> f64) -> Self {
+        Self::new(spectrum::spectrum_to_color(spectral_radiance))
+    }
+
+    /// Emits a luminous flux in lumens with the color of a black body at a temperature in Kelvin,
+    /// spread over an emitting surface area in square meters.
+    #[must_use]
+    pub fn from_lumens(temperature: f64, lumens: f64, area: f64) -> Self {
+        Self::blackbody(temperature, lumens / (PI * area))
+    }
+
+    /// Emits a radiant power in watts with the color and luminous efficacy of a black body at a
+    /// temperature in Kelvin, spread over an emitting surface area in square meters.
+    #[must_use]
+    pub fn from_watts(temperature: f64, watts: f64, area: f64) -> Self {
+        let lumens = watts * spectrum::blackbody_luminous_efficacy(temperature);
+        Self::from_lumens(temperature, lumens, area)
+    }
+}
+
+impl Material for Emitter {
+    fn scatter(
+        &self,
+        _ray_in: &Ray,
+        _record: &HitRecord,
+        _sampler: &mut dyn Sampler,
+    ) -> Option<(Color, Ray)> {
+        None
+    }
+
+    fn emitted(&self, _ray_in: &Ray, record: &HitRecord) -> Color {
+        if record.front_face() {
+            self.radiance
+        } else {
+            Color::default()
+        }
+    }
+}
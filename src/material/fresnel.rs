@@ -0,0 +1,29 @@
+/// Exact unpolarized Fresnel reflectance of a smooth dielectric interface.
+///
+/// `refraction_ratio` is the ratio of the refraction index on the incident side over the one on
+/// the transmitted side, matching the convention of `Vec3::refract`. Returns 1 on total internal
+/// reflection.
+#[must_use]
+pub fn dielectric(cosine: f64, refraction_ratio: f64) -> f64 {
+    let cos_theta_i = cosine.clamp(0.0, 1.0);
+    let sin2_theta_t = refraction_ratio * refraction_ratio * (1.0 - cos_theta_i * cos_theta_i);
+    if sin2_theta_t >= 1.0 {
+        return 1.0;
+    }
+    let cos_theta_t = (1.0 - sin2_theta_t).sqrt();
+    let r_perpendicular = (refraction_ratio * cos_theta_i - cos_theta_t)
+        / (refraction_ratio * cos_theta_i + cos_theta_t);
+    let r_parallel = (cos_theta_i - refraction_ratio * cos_theta_t)
+        / (cos_theta_i + refraction_ratio * cos_theta_t);
+    0.5 * (r_perpendicular * r_perpendicular + r_parallel * r_parallel)
+}
+
+/// Combined reflectance of both faces of a thin slab, summing all incoherent internal bounces.
+#[must_use]
+pub fn slab(top: f64, bottom: f64) -> f64 {
+    let denominator = 1.0 - top * bottom;
+    if denominator <= 0.0 {
+        return 1.0;
+    }
+    top + (1.0 - top) * (1.0 - top) * bottom / denominator
+}
@@ -1,5 +1,8 @@
+use std::f64::consts::PI;
+
 use crate::{
     ray::Ray,
+    sampler::Sampler,
     vec3::{Color, Vec3},
 };
 
@@ -22,8 +25,9 @@ impl Material for Lambertian {
         &self,
         _ray_in: &crate::ray::Ray,
         record: &crate::hittable::HitRecord,
+        sampler: &mut dyn Sampler,
     ) -> Option<(Color, crate::ray::Ray)> {
-        let mut scatter_direction = record.normal() + Vec3::random_unit_vector();
+        let mut scatter_direction = record.normal() + Vec3::sample_unit_vector(sampler.get_2d());
 
         // Catch degenerate scatter_direction
         if scatter_direction.near_zero() {
@@ -34,4 +38,21 @@ impl Material for Lambertian {
         let attenuation = self.albedo;
         Some((attenuation, scattered))
     }
+
+    fn evaluate(
+        &self,
+        _ray_in: &crate::ray::Ray,
+        record: &crate::hittable::HitRecord,
+        direction: Vec3,
+    ) -> Color {
+        let cosine = record.normal().dot(&direction);
+        if cosine <= 0.0 || record.geometric_normal().dot(&direction) <= 0.0 {
+            return Color::default();
+        }
+        self.albedo * (cosine / PI)
+    }
+
+    fn albedo(&self, _record: &crate::hittable::HitRecord) -> Color {
+        self.albedo
+    }
 }
@@ -1,5 +1,8 @@
+use std::f64::consts::PI;
+
 use crate::{
     ray::Ray,
+    sampler::Sampler,
     vec3::{Color, Vec3},
 };
 
@@ -26,11 +29,48 @@ impl Material for Metal {
         &self,
         ray_in: &crate::ray::Ray,
         record: &crate::hittable::HitRecord,
+        sampler: &mut dyn Sampler,
     ) -> Option<(Color, crate::ray::Ray)> {
         let reflected = ray_in.direction().reflect(&record.normal()).unit_vector()
-            + (self.fuzz * Vec3::random_unit_vector());
+            + (self.fuzz * Vec3::sample_unit_vector(sampler.get_2d()));
         let scattered = Ray::new(record.point(), reflected);
         let attenuation = self.albedo;
-        (scattered.direction().dot(&record.normal()) > 0.0).then_some((attenuation, scattered))
+        (scattered.direction().dot(&record.geometric_normal()) > 0.0)
+            .then_some((attenuation, scattered))
+    }
+
+    /// The fuzzy reflection is uniform over the sphere with the fuzz as radius around the mirrored
+    /// direction, so the light scattered from a direction is the albedo times the density of
+    /// the directions through that sphere.
+    fn evaluate(
+        &self,
+        ray_in: &Ray,
+        record: &crate::hittable::HitRecord,
+        direction: Vec3,
+    ) -> Color {
+        if self.fuzz <= 0.0 || record.geometric_normal().dot(&direction) <= 0.0 {
+            return Color::default();
+        }
+        let reflected = ray_in.direction().reflect(&record.normal()).unit_vector();
+        let direction = direction.unit_vector();
+
+        // Distances t along the direction to the sphere solve t² - 2ct + 1 - fuzz² = 0. Each
+        // intersection contributes t² / (4π fuzz sqrt(discriminant)) to the solid angle density.
+        let c = direction.dot(&reflected);
+        let discriminant = c * c - 1.0 + self.fuzz * self.fuzz;
+        if discriminant <= 0.0 {
+            return Color::default();
+        }
+        let root = discriminant.sqrt();
+        let density: f64 = [c - root, c + root]
+            .into_iter()
+            .filter(|&t| t > 0.0)
+            .map(|t| t * t / (4.0 * PI * self.fuzz * root))
+            .sum();
+        self.albedo * density
+    }
+
+    fn albedo(&self, _record: &crate::hittable::HitRecord) -> Color {
+        self.albedo
     }
 }
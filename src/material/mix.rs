@@ -0,0 +1,83 @@
+use std::sync::Arc;
+
+use crate::{
+    hittable::HitRecord,
+    ray::Ray,
+    sampler::Sampler,
+    texture::{Texture, solid_color::SolidColor},
+    vec3::{Color, Vec3},
+};
+
+use super::Material;
+
+/// Blends two materials, scattering by the second one for a fraction of the hits given by the
+/// weight, read as the average of the texture channels.
+#[derive(Debug, Clone)]
+pub struct Mix {
+    first: Arc<dyn Material>,
+    second: Arc<dyn Material>,
+    weight: Arc<dyn Texture>,
+}
+
+impl Mix {
+    #[must_use]
+    pub fn new(first: Arc<dyn Material>, second: Arc<dyn Material>, weight: f64) -> Self {
+        Self::with_texture(
+            first,
+            second,
+            Arc::new(SolidColor::new(Color::new([weight; 3]))),
+        )
+    }
+
+    #[must_use]
+    pub const fn with_texture(
+        first: Arc<dyn Material>,
+        second: Arc<dyn Material>,
+        weight: Arc<dyn Texture>,
+    ) -> Self {
+        Self {
+            first,
+            second,
+            weight,
+        }
+    }
+}
+
+impl Mix {
+    fn weight(&self, record: &HitRecord) -> f64 {
+        let value = self.weight.value(record.u(), record.v(), record.point());
+        (value.x() + value.y() + value.z()) / 3.0
+    }
+}
+
+impl Material for Mix {
+    fn scatter(
+        &self,
+        ray_in: &Ray,
+        record: &HitRecord,
+        sampler: &mut dyn Sampler,
+    ) -> Option<(Color, Ray)> {
+        if self.weight(record) > sampler.get_1d() {
+            self.second.scatter(ray_in, record, sampler)
+        } else {
+            self.first.scatter(ray_in, record, sampler)
+        }
+    }
+
+    fn emitted(&self, ray_in: &Ray, record: &HitRecord) -> Color {
+        let weight = self.weight(record);
+        (1.0 - weight) * self.first.emitted(ray_in, record)
+            + weight * self.second.emitted(ray_in, record)
+    }
+
+    fn evaluate(&self, ray_in: &Ray, record: &HitRecord, direction: Vec3) -> Color {
+        let weight = self.weight(record);
+        (1.0 - weight) * self.first.evaluate(ray_in, record, direction)
+            + weight * self.second.evaluate(ray_in, record, direction)
+    }
+
+    fn albedo(&self, record: &HitRecord) -> Color {
+        let weight = self.weight(record);
+        (1.0 - weight) * self.first.albedo(record) + weight * self.second.albedo(record)
+    }
+}
@@ -0,0 +1,115 @@
+use std::{f64::consts::PI, sync::Arc};
+
+use crate::{
+    hittable::HitRecord,
+    ray::Ray,
+    sampler::Sampler,
+    texture::{Texture, solid_color::SolidColor},
+    vec3::{Color, Vec3},
+};
+
+use super::Material;
+
+/// A rough diffuse surface, like clay, concrete or cloth, which scatters more light back towards
+/// its source than a Lambertian one.
+#[derive(Debug, Clone)]
+pub struct OrenNayar {
+    albedo: Arc<dyn Texture>,
+
+    /// Constant term of the Oren-Nayar approximation, 1 for a smooth surface
+    a: f64,
+
+    /// Directional term of the Oren-Nayar approximation, 0 for a smooth surface
+    b: f64,
+}
+
+impl OrenNayar {
+    /// Creates a surface with the given standard deviation of the microfacet slope angle in
+    /// degrees. A deviation of 0 is a Lambertian surface.
+    #[must_use]
+    pub fn new(albedo: Color, sigma: f64) -> Self {
+        Self::with_texture(Arc::new(SolidColor::new(albedo)), sigma)
+    }
+
+    #[must_use]
+    pub fn with_texture(albedo: Arc<dyn Texture>, sigma: f64) -> Self {
+        let sigma2 = sigma.to_radians().powi(2);
+        Self {
+            albedo,
+            a: 1.0 - sigma2 / (2.0 * (sigma2 + 0.33)),
+            b: 0.45 * sigma2 / (sigma2 + 0.09),
+        }
+    }
+
+    /// Ratio of the Oren-Nayar over the Lambertian reflectance for a pair of unit directions
+    fn factor(&self, normal: Vec3, incoming: Vec3, outgoing: Vec3) -> f64 {
+        if self.b <= 0.0 {
+            return self.a;
+        }
+        let cos_in = normal.dot(&incoming).clamp(0.0, 1.0);
+        let cos_out = normal.dot(&outgoing).clamp(0.0, 1.0);
+        let sin_in = (1.0 - cos_in * cos_in).sqrt();
+        let sin_out = (1.0 - cos_out * cos_out).sqrt();
+
+        // Cosine of the azimuth between both directions
+        let tangent_in = incoming - cos_in * normal;
+        let tangent_out = outgoing - cos_out * normal;
+        let cos_azimuth = if tangent_in.near_zero() || tangent_out.near_zero() {
+            0.0
+        } else {
+            tangent_in.unit_vector().dot(&tangent_out.unit_vector())
+        };
+
+        // The sine of the largest and tangent of the smallest angle to the normal
+        let (sin_alpha, tan_beta) = if cos_in > cos_out {
+            (sin_out, sin_in / cos_in)
+        } else {
+            (sin_in, sin_out / cos_out.max(1e-8))
+        };
+        self.a + self.b * cos_azimuth.max(0.0) * sin_alpha * tan_beta
+    }
+}
+
+impl Material for OrenNayar {
+    fn scatter(
+        &self,
+        ray_in: &Ray,
+        record: &HitRecord,
+        sampler: &mut dyn Sampler,
+    ) -> Option<(Color, Ray)> {
+        let normal = record.normal();
+        let mut scatter_direction = normal + Vec3::sample_unit_vector(sampler.get_2d());
+
+        // Catch degenerate scatter_direction
+        if scatter_direction.near_zero() {
+            scatter_direction = normal;
+        }
+
+        // The directions are cosine distributed, so only the Oren-Nayar factor on top of the
+        // Lambertian reflectance remains.
+        let albedo = self.albedo.value(record.u(), record.v(), record.point());
+        let factor = self.factor(
+            normal,
+            scatter_direction.unit_vector(),
+            -ray_in.direction().unit_vector(),
+        );
+
+        let scattered = Ray::new(record.point(), scatter_direction);
+        Some((albedo * factor, scattered))
+    }
+
+    fn evaluate(&self, ray_in: &Ray, record: &HitRecord, direction: Vec3) -> Color {
+        let normal = record.normal();
+        let cosine = normal.dot(&direction);
+        if cosine <= 0.0 || record.geometric_normal().dot(&direction) <= 0.0 {
+            return Color::default();
+        }
+        let albedo = self.albedo.value(record.u(), record.v(), record.point());
+        let factor = self.factor(normal, direction, -ray_in.direction().unit_vector());
+        albedo * (factor * cosine / PI)
+    }
+
+    fn albedo(&self, record: &HitRecord) -> Color {
+        self.albedo.value(record.u(), record.v(), record.point())
+    }
+}
@@ -0,0 +1,113 @@
+use crate::{
+    hittable::HitRecord,
+    ray::Ray,
+    sampler::Sampler,
+    vec3::{Color, Vec3},
+};
+
+use super::{Material, dielectric::Dielectric};
+
+/// A translucent material, like skin, wax, marble or milk, where light enters through a smooth
+/// dielectric boundary and performs a random walk through a homogeneous medium filling the closed
+/// object until it leaves again.
+#[derive(Debug, Clone, Copy)]
+pub struct Subsurface {
+    refraction_index: f64,
+
+    /// Scattering coefficient per color channel, per unit of distance
+    scattering: Color,
+
+    /// Extinction coefficient, the sum of the absorption and scattering coefficient
+    extinction: Color,
+}
+
+impl Subsurface {
+    #[must_use]
+    pub fn new(refraction_index: f64, absorption: Color, scattering: Color) -> Self {
+        Self {
+            refraction_index,
+            scattering,
+            extinction: absorption + scattering,
+        }
+    }
+
+    /// Creates the medium from the fraction of light scattered at each interaction and the mean
+    /// distance between interactions, per color channel.
+    #[must_use]
+    pub fn from_mean_free_path(
+        refraction_index: f64,
+        albedo: Color,
+        mean_free_path: Color,
+    ) -> Self {
+        let mut extinction = Color::default();
+        for channel in 0..3 {
+            extinction[channel] = 1.0 / mean_free_path[channel];
+        }
+        Self {
+            refraction_index,
+            scattering: albedo * extinction,
+            extinction,
+        }
+    }
+
+    fn transmittance(&self, distance: f64) -> Color {
+        let mut transmittance = Color::default();
+        for channel in 0..3 {
+            transmittance[channel] = (-self.extinction[channel] * distance).exp();
+        }
+        transmittance
+    }
+}
+
+impl Material for Subsurface {
+    fn scatter(
+        &self,
+        ray_in: &Ray,
+        record: &HitRecord,
+        sampler: &mut dyn Sampler,
+    ) -> Option<(Color, Ray)> {
+        let unit_direction = ray_in.direction().unit_vector();
+        if record.front_face() {
+            // Entering the object through its boundary
+            let direction = Dielectric::reflect_or_refract(
+                unit_direction,
+                record.normal(),
+                1.0 / self.refraction_index,
+                sampler.get_1d(),
+            );
+            return Some((Color::new([1.0; 3]), Ray::new(record.point(), direction)));
+        }
+
+        // The ray traveled through the medium up to the boundary. Sample the distance to the first
+        // interaction along it using the extinction of a random channel, weighting by the average
+        // probability density over all channels.
+        let distance_to_boundary = record.time() * ray_in.direction().length();
+        let [u1, u2] = sampler.get_2d();
+        #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let channel = ((3.0 * u1) as usize).min(2);
+        let distance = -(1.0 - u2).ln() / self.extinction[channel];
+
+        if distance < distance_to_boundary {
+            let transmittance = self.transmittance(distance);
+            let density = self.extinction * transmittance;
+            let probability = (density.x() + density.y() + density.z()) / 3.0;
+            let point = ray_in.origin() + distance * unit_direction;
+            let scattered = Ray::new(point, Vec3::sample_unit_vector(sampler.get_2d()));
+            return Some((self.scattering * transmittance / probability, scattered));
+        }
+
+        // Reached the boundary without interacting, where the ray leaves or reflects back inside
+        let transmittance = self.transmittance(distance_to_boundary);
+        let probability = (transmittance.x() + transmittance.y() + transmittance.z()) / 3.0;
+        let direction = Dielectric::reflect_or_refract(
+            unit_direction,
+            record.normal(),
+            self.refraction_index,
+            sampler.get_1d(),
+        );
+        Some((
+            transmittance / probability,
+            Ray::new(record.point(), direction),
+        ))
+    }
+}
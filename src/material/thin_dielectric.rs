@@ -0,0 +1,64 @@
+use crate::{hittable::HitRecord, ray::Ray, sampler::Sampler, vec3::Color};
+
+use super::{Material, fresnel, thin_film::ThinFilm};
+
+/// An infinitely thin dielectric slab, like a window pane. Light is either reflected or passes
+/// through unbent, as both faces refract the ray by opposite amounts.
+#[derive(Debug, Clone, Copy)]
+pub struct ThinDielectric {
+    refraction_index: f64,
+    coating: Option<ThinFilm>,
+}
+
+impl ThinDielectric {
+    #[must_use]
+    pub const fn new(refraction_index: f64) -> Self {
+        Self {
+            refraction_index,
+            coating: None,
+        }
+    }
+
+    /// Covers the slab with a thin film. A soap bubble is a film without a slab underneath, which
+    /// is a slab with a refraction index of 1.
+    #[must_use]
+    pub const fn with_coating(mut self, coating: ThinFilm) -> Self {
+        self.coating = Some(coating);
+        self
+    }
+}
+
+impl Material for ThinDielectric {
+    fn scatter(
+        &self,
+        ray_in: &Ray,
+        record: &HitRecord,
+        sampler: &mut dyn Sampler,
+    ) -> Option<(Color, Ray)> {
+        let unit_direction = ray_in.direction().unit_vector();
+        let cos_theta = record.normal().dot(&-unit_direction).min(1.0);
+        let bottom = fresnel::dielectric(cos_theta, 1.0 / self.refraction_index);
+        let reflectance = match self.coating {
+            Some(coating) => {
+                let mut top = coating.reflectance(cos_theta, 1.0, self.refraction_index);
+                for channel in 0..3 {
+                    top[channel] = fresnel::slab(top[channel], bottom);
+                }
+                top
+            }
+            None => Color::new([fresnel::slab(bottom, bottom); 3]),
+        };
+
+        // Choose between reflection and transmission proportional to the average reflectance, and
+        // weight the chosen path to keep the color of each channel.
+        let reflect_probability = (reflectance.x() + reflectance.y() + reflectance.z()) / 3.0;
+        if reflect_probability > sampler.get_1d() {
+            let direction = unit_direction.reflect(&record.normal());
+            let attenuation = reflectance / reflect_probability;
+            Some((attenuation, Ray::new(record.point(), direction)))
+        } else {
+            let attenuation = (Color::new([1.0; 3]) - reflectance) / (1.0 - reflect_probability);
+            Some((attenuation, Ray::new(record.point(), unit_direction)))
+        }
+    }
+}
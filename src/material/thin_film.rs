@@ -0,0 +1,69 @@
+use std::f64::consts::PI;
+
+use crate::vec3::Color;
+
+/// Wavelengths in nanometers used for the red, green and blue channels.
+const WAVELENGTHS: [f64; 3] = [650.0, 532.0, 450.0];
+
+/// A thin transparent layer on top of a surface, causing interference between the light reflected
+/// at its top and bottom, like oil on water or the wall of a soap bubble.
+#[derive(Debug, Clone, Copy)]
+pub struct ThinFilm {
+    /// Film thickness in nanometers
+    thickness: f64,
+
+    /// Refraction index of the film
+    refraction_index: f64,
+}
+
+impl ThinFilm {
+    #[must_use]
+    pub const fn new(thickness: f64, refraction_index: f64) -> Self {
+        Self {
+            thickness: thickness.max(0.0),
+            refraction_index,
+        }
+    }
+
+    /// Reflectance per color channel of the film lying between a medium with refraction index
+    /// `outer_index` on the incident side and a substrate with refraction index `inner_index`.
+    #[must_use]
+    pub fn reflectance(&self, cosine: f64, outer_index: f64, inner_index: f64) -> Color {
+        let cos_outer = cosine.clamp(0.0, 1.0);
+        let sin2_outer = 1.0 - cos_outer * cos_outer;
+
+        let sin2_film = (outer_index / self.refraction_index).powi(2) * sin2_outer;
+        let sin2_inner = (outer_index / inner_index).powi(2) * sin2_outer;
+        if sin2_film >= 1.0 || sin2_inner >= 1.0 {
+            // Total internal reflection at either face of a lossless film reflects everything.
+            return Color::new([1.0; 3]);
+        }
+        let cos_film = (1.0 - sin2_film).sqrt();
+        let cos_inner = (1.0 - sin2_inner).sqrt();
+
+        // Amplitude coefficients of the top and bottom face, for both polarizations
+        let (n1, n2, n3) = (outer_index, self.refraction_index, inner_index);
+        let top_perpendicular = (n1 * cos_outer - n2 * cos_film) / (n1 * cos_outer + n2 * cos_film);
+        let bottom_perpendicular =
+            (n2 * cos_film - n3 * cos_inner) / (n2 * cos_film + n3 * cos_inner);
+        let top_parallel = (n2 * cos_outer - n1 * cos_film) / (n2 * cos_outer + n1 * cos_film);
+        let bottom_parallel = (n3 * cos_film - n2 * cos_inner) / (n3 * cos_film + n2 * cos_inner);
+
+        let mut reflectance = Color::default();
+        for (channel, wavelength) in WAVELENGTHS.into_iter().enumerate() {
+            // Phase difference between the light reflected at the top and bottom face
+            let phase = 4.0 * PI * n2 * self.thickness * cos_film / wavelength;
+            reflectance[channel] = 0.5
+                * (airy(top_perpendicular, bottom_perpendicular, phase)
+                    + airy(top_parallel, bottom_parallel, phase));
+        }
+        reflectance
+    }
+}
+
+/// Reflectance of a film given the amplitude coefficients of both faces and their phase
+/// difference, summing all coherent internal reflections.
+fn airy(top: f64, bottom: f64, phase: f64) -> f64 {
+    let cross = 2.0 * top * bottom * phase.cos();
+    (top * top + bottom * bottom + cross) / (1.0 + top * top * bottom * bottom + cross)
+}
@@ -0,0 +1,92 @@
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+use image::{ImageFormat, ImageResult, Rgb, Rgb32FImage, RgbImage};
+
+use crate::{display::DisplayTransform, vec3::Color};
+
+/// File format a rendered image is saved in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// 8-bit image in any format the `image` crate can write, chosen by the file extension
+    Display,
+
+    /// Linear 32-bit floating point `OpenEXR`
+    OpenExr,
+
+    /// Linear Radiance RGBE
+    RadianceHdr,
+
+    /// Linear 32-bit floating point Portable Float Map
+    Pfm,
+}
+
+impl OutputFormat {
+    /// Chooses the format by the file extension, using an 8-bit image for anything but `.exr`,
+    /// `.hdr` and `.pfm`.
+    #[must_use]
+    pub fn from_path(path: &Path) -> Self {
+        match path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_ascii_lowercase)
+            .as_deref()
+        {
+            Some("exr") => Self::OpenExr,
+            Some("hdr") => Self::RadianceHdr,
+            Some("pfm") => Self::Pfm,
+            _ => Self::Display,
+        }
+    }
+}
+
+/// Creates a linear floating point image from the color of every pixel.
+#[must_use]
+#[expect(clippy::cast_possible_truncation)]
+pub fn linear_image(width: u32, height: u32, color: impl Fn(u32, u32) -> Color) -> Rgb32FImage {
+    Rgb32FImage::from_fn(width, height, |x, y| {
+        let color = color(x, y);
+        Rgb([color.x() as f32, color.y() as f32, color.z() as f32])
+    })
+}
+
+/// Saves a linear image in the given format. Formats which can't hold floating point values get
+/// the image converted by the display transform, while the others keep the linear radiance.
+///
+/// # Errors
+/// Returns an error if the image couldn't be encoded or written.
+pub fn save(
+    image: &Rgb32FImage,
+    path: &Path,
+    format: OutputFormat,
+    display: &DisplayTransform,
+) -> ImageResult<()> {
+    match format {
+        OutputFormat::Display => RgbImage::from_fn(image.width(), image.height(), |x, y| {
+            let Rgb(channels) = image.get_pixel(x, y);
+            display.apply(Color::new(channels.map(f64::from)))
+        })
+        .save(path),
+        OutputFormat::OpenExr => image.save_with_format(path, ImageFormat::OpenExr),
+        OutputFormat::RadianceHdr => image.save_with_format(path, ImageFormat::Hdr),
+        OutputFormat::Pfm => save_pfm(image, path),
+    }
+}
+
+/// Writes a color Portable Float Map: a text header followed by little-endian floats, with the
+/// rows from bottom to top.
+fn save_pfm(image: &Rgb32FImage, path: &Path) -> ImageResult<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    // A negative scale marks the data as little-endian
+    write!(writer, "PF\n{} {}\n-1.0\n", image.width(), image.height())?;
+    for row in image.rows().rev() {
+        for value in row.flat_map(|pixel| pixel.0) {
+            writer.write_all(&value.to_le_bytes())?;
+        }
+    }
+    writer.flush()?;
+    Ok(())
+}
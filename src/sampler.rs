@@ -0,0 +1,90 @@
+use std::fmt::Debug;
+
+pub mod halton;
+pub mod independent;
+pub mod sobol;
+pub mod stratified;
+
+/// Generates the numbers in [0, 1) from which a sample of the light through a pixel is built.
+/// Every call draws the next dimension of the current sample, so the numbers used for the same
+/// purpose in different samples of a pixel can be well distributed relative to each other.
+///
+/// The numbers only depend on the seed, pixel, sample index and dimension, so a render is the same
+/// every time regardless of the order in which pixels are rendered.
+pub trait Sampler: Debug + Send + Sync {
+    /// Sets the seed from which all numbers are derived.
+    fn set_seed(&mut self, seed: u64);
+
+    /// Sets the maximum number of samples taken per pixel, over which samplers with a fixed number
+    /// of samples distribute them. Samplers generating unbounded sequences ignore it.
+    fn set_sample_count(&mut self, _samples_per_pixel: u32) {}
+
+    /// Starts the sample with the given index in the pixel x, y, starting again at the first
+    /// dimension.
+    fn start_pixel_sample(&mut self, x: u32, y: u32, sample_index: u32);
+
+    fn get_1d(&mut self) -> f64;
+
+    fn get_2d(&mut self) -> [f64; 2];
+
+    /// Returns a sampler generating the same pattern with its own state.
+    fn clone_box(&self) -> Box<dyn Sampler>;
+}
+
+/// Hashes a sequence of values into a single well mixed value.
+pub fn hash(values: impl IntoIterator<Item = u64>) -> u64 {
+    values
+        .into_iter()
+        .fold(0x9e37_79b9_7f4a_7c15_u64, |state, value| mix(state ^ value))
+}
+
+/// Maps a hash to a number in [0, 1).
+#[must_use]
+#[expect(clippy::cast_precision_loss)]
+pub fn hash_to_unit(hash: u64) -> f64 {
+    (hash >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// The finalizer of the `SplitMix64` generator
+const fn mix(mut state: u64) -> u64 {
+    state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    state = (state ^ (state >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    state = (state ^ (state >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    state ^ (state >> 31)
+}
+
+/// Returns the element at an index of a pseudo-random permutation of [0, length), selected by the
+/// given seed, as described by Kensler in "Correlated Multi-Jittered Sampling".
+#[must_use]
+pub const fn permutation_element(mut index: u32, length: u32, seed: u32) -> u32 {
+    let mut mask = length.saturating_sub(1);
+    mask |= mask >> 1;
+    mask |= mask >> 2;
+    mask |= mask >> 4;
+    mask |= mask >> 8;
+    mask |= mask >> 16;
+    loop {
+        index ^= seed;
+        index = index.wrapping_mul(0xe170_893d);
+        index ^= seed >> 16;
+        index ^= (index & mask) >> 4;
+        index ^= seed >> 8;
+        index = index.wrapping_mul(0x0929_eb3f);
+        index ^= seed >> 23;
+        index ^= (index & mask) >> 1;
+        index = index.wrapping_mul(1 | seed >> 27);
+        index = index.wrapping_mul(0x6935_fa69);
+        index ^= (index & mask) >> 11;
+        index = index.wrapping_mul(0x74dc_b303);
+        index ^= (index & mask) >> 2;
+        index = index.wrapping_mul(0x9e50_1cc3);
+        index ^= (index & mask) >> 2;
+        index = index.wrapping_mul(0xc860_a3df);
+        index &= mask;
+        index ^= index >> 5;
+        if index < length {
+            break;
+        }
+    }
+    (index.wrapping_add(seed)) % length
+}
@@ -0,0 +1,82 @@
+use super::{Sampler, hash, hash_to_unit};
+
+/// Bases of the dimensions of the sequence
+const PRIMES: [u32; 32] = [
+    2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97,
+    101, 103, 107, 109, 113, 127, 131,
+];
+
+/// Draws the samples of a pixel from the Halton sequence, with each dimension shifted by a random
+/// offset per pixel to avoid correlation between pixels. Dimensions beyond the supported bases are
+/// drawn from a hash.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Halton {
+    seed: u64,
+    pixel: [u32; 2],
+    sample_index: u32,
+    dimension: u32,
+}
+
+impl Halton {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            seed: 0,
+            pixel: [0; 2],
+            sample_index: 0,
+            dimension: 0,
+        }
+    }
+}
+
+/// Mirrors the digits of an index in the given base around the radix point.
+fn radical_inverse(base: u32, mut index: u32) -> f64 {
+    let inverse_base = 1.0 / f64::from(base);
+    let mut reversed = 0.0;
+    let mut inverse_base_power = 1.0;
+    while index > 0 {
+        let next = index / base;
+        let digit = index - next * base;
+        reversed = reversed * f64::from(base) + f64::from(digit);
+        inverse_base_power *= inverse_base;
+        index = next;
+    }
+    (reversed * inverse_base_power).min(1.0 - f64::EPSILON)
+}
+
+impl Sampler for Halton {
+    fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+    }
+
+    fn start_pixel_sample(&mut self, x: u32, y: u32, sample_index: u32) {
+        self.pixel = [x, y];
+        self.sample_index = sample_index;
+        self.dimension = 0;
+    }
+
+    fn get_1d(&mut self) -> f64 {
+        let pixel_hash = hash([
+            self.seed,
+            u64::from(self.pixel[0]),
+            u64::from(self.pixel[1]),
+            u64::from(self.dimension),
+        ]);
+        let value = match PRIMES.get(self.dimension as usize) {
+            Some(&base) => {
+                (radical_inverse(base, self.sample_index) + hash_to_unit(pixel_hash)).fract()
+            }
+            None => hash_to_unit(hash([pixel_hash, u64::from(self.sample_index)])),
+        };
+        self.dimension += 1;
+        value
+    }
+
+    fn get_2d(&mut self) -> [f64; 2] {
+        [self.get_1d(), self.get_1d()]
+    }
+
+    fn clone_box(&self) -> Box<dyn Sampler> {
+        Box::new(*self)
+    }
+}
@@ -0,0 +1,53 @@
+use super::{Sampler, hash, hash_to_unit};
+
+/// Draws every number independently and uniformly at random.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Independent {
+    seed: u64,
+
+    /// Hash of the seed, pixel and sample index of the current sample
+    sample_hash: u64,
+
+    dimension: u32,
+}
+
+impl Independent {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            seed: 0,
+            sample_hash: 0,
+            dimension: 0,
+        }
+    }
+}
+
+impl Sampler for Independent {
+    fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+    }
+
+    fn start_pixel_sample(&mut self, x: u32, y: u32, sample_index: u32) {
+        self.sample_hash = hash([
+            self.seed,
+            u64::from(x),
+            u64::from(y),
+            u64::from(sample_index),
+        ]);
+        self.dimension = 0;
+    }
+
+    fn get_1d(&mut self) -> f64 {
+        let value = hash_to_unit(hash([self.sample_hash, u64::from(self.dimension)]));
+        self.dimension += 1;
+        value
+    }
+
+    fn get_2d(&mut self) -> [f64; 2] {
+        [self.get_1d(), self.get_1d()]
+    }
+
+    fn clone_box(&self) -> Box<dyn Sampler> {
+        Box::new(*self)
+    }
+}
@@ -0,0 +1,121 @@
+use super::{Sampler, hash, permutation_element};
+
+/// Draws every pair of dimensions from the first two dimensions of the Sobol sequence, Owen
+/// scrambled per pixel and dimension. The order of the samples is shuffled differently for each
+/// pair, which keeps the dimensions uncorrelated while each pair stays well stratified.
+#[derive(Debug, Clone, Copy)]
+pub struct Sobol {
+    seed: u64,
+    samples_per_pixel: u32,
+    pixel: [u32; 2],
+    sample_index: u32,
+    dimension: u32,
+}
+
+impl Sobol {
+    /// Creates the sampler, which works best when the number of samples per pixel set by the
+    /// camera is a power of two.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            seed: 0,
+            samples_per_pixel: 1,
+            pixel: [0; 2],
+            sample_index: 0,
+            dimension: 0,
+        }
+    }
+
+    /// Returns the shuffled index and the scrambling seeds for the current dimension.
+    #[expect(clippy::cast_possible_truncation)]
+    fn start_dimension(&self) -> (u32, [u32; 2]) {
+        let seed = hash([
+            self.seed,
+            u64::from(self.pixel[0]),
+            u64::from(self.pixel[1]),
+            u64::from(self.dimension),
+        ]);
+        let index = permutation_element(
+            self.sample_index % self.samples_per_pixel,
+            self.samples_per_pixel,
+            seed as u32,
+        );
+        let scramble = hash([seed]);
+        (index, [scramble as u32, (scramble >> 32) as u32])
+    }
+}
+
+/// Returns the first (dimension 0) or second (dimension 1) dimension of the Sobol sequence.
+const fn sobol(index: u32, dimension: u32) -> u32 {
+    if dimension == 0 {
+        return index.reverse_bits();
+    }
+    let mut result = 0;
+    let mut direction = 1 << 31;
+    let mut index = index;
+    while index != 0 {
+        if index & 1 != 0 {
+            result ^= direction;
+        }
+        direction ^= direction >> 1;
+        index >>= 1;
+    }
+    result
+}
+
+/// Randomly flips the digits of a value, each depending on the digits before it, which keeps the
+/// stratification of the sequence.
+const fn owen_scramble(value: u32, seed: u32) -> u32 {
+    let mut value = value.reverse_bits();
+    value ^= value.wrapping_mul(0x3d20_adea);
+    value = value.wrapping_add(seed);
+    value = value.wrapping_mul((seed >> 16) | 1);
+    value ^= value.wrapping_mul(0x0552_6c56);
+    value ^= value.wrapping_mul(0x53a2_2864);
+    value.reverse_bits()
+}
+
+fn to_unit(value: u32) -> f64 {
+    f64::from(value) / 4_294_967_296.0
+}
+
+impl Default for Sobol {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Sampler for Sobol {
+    fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+    }
+
+    fn set_sample_count(&mut self, samples_per_pixel: u32) {
+        self.samples_per_pixel = samples_per_pixel.max(1);
+    }
+
+    fn start_pixel_sample(&mut self, x: u32, y: u32, sample_index: u32) {
+        self.pixel = [x, y];
+        self.sample_index = sample_index;
+        self.dimension = 0;
+    }
+
+    fn get_1d(&mut self) -> f64 {
+        let (index, [seed, _]) = self.start_dimension();
+        self.dimension += 1;
+        to_unit(owen_scramble(sobol(index, 0), seed))
+    }
+
+    fn get_2d(&mut self) -> [f64; 2] {
+        let (index, seeds) = self.start_dimension();
+        self.dimension += 2;
+        [
+            to_unit(owen_scramble(sobol(index, 0), seeds[0])),
+            to_unit(owen_scramble(sobol(index, 1), seeds[1])),
+        ]
+    }
+
+    fn clone_box(&self) -> Box<dyn Sampler> {
+        Box::new(*self)
+    }
+}
@@ -0,0 +1,103 @@
+use super::{Sampler, hash, hash_to_unit, permutation_element};
+
+/// Divides every dimension into as many equal strata as there are samples per pixel, and draws
+/// one jittered number from each stratum. Pairs of dimensions are divided into a grid of at least
+/// as many cells, as square as possible.
+#[derive(Debug, Clone, Copy)]
+pub struct Stratified {
+    seed: u64,
+    samples_per_pixel: u32,
+
+    /// Number of grid cells along the first and second of pairs of dimensions
+    grid_size: [u32; 2],
+
+    pixel: [u32; 2],
+    sample_index: u32,
+    dimension: u32,
+}
+
+impl Stratified {
+    /// Creates the sampler, which stratifies the number of samples per pixel set by the camera.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            seed: 0,
+            samples_per_pixel: 1,
+            grid_size: [1; 2],
+            pixel: [0; 2],
+            sample_index: 0,
+            dimension: 0,
+        }
+    }
+
+    /// Returns the stratum of the current sample among the given number of strata. Each dimension
+    /// of each pixel visits the strata in a different order.
+    #[expect(clippy::cast_possible_truncation)]
+    fn stratum(&self, strata: u32) -> u32 {
+        let seed = hash([
+            self.seed,
+            u64::from(self.pixel[0]),
+            u64::from(self.pixel[1]),
+            u64::from(self.dimension),
+        ]);
+        permutation_element(self.sample_index % strata, strata, seed as u32)
+    }
+
+    /// Returns the jitter within the stratum for the given dimension of the current sample.
+    fn jitter(&self, dimension: u32) -> f64 {
+        hash_to_unit(hash([
+            self.seed,
+            u64::from(self.pixel[0]),
+            u64::from(self.pixel[1]),
+            u64::from(self.sample_index),
+            u64::from(dimension),
+        ]))
+    }
+}
+
+impl Default for Stratified {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Sampler for Stratified {
+    fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+    }
+
+    #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn set_sample_count(&mut self, samples_per_pixel: u32) {
+        self.samples_per_pixel = samples_per_pixel.max(1);
+        let width = (f64::from(self.samples_per_pixel).sqrt() as u32).max(1);
+        self.grid_size = [width, self.samples_per_pixel.div_ceil(width)];
+    }
+
+    fn start_pixel_sample(&mut self, x: u32, y: u32, sample_index: u32) {
+        self.pixel = [x, y];
+        self.sample_index = sample_index;
+        self.dimension = 0;
+    }
+
+    fn get_1d(&mut self) -> f64 {
+        let stratum = self.stratum(self.samples_per_pixel);
+        let jitter = self.jitter(self.dimension);
+        self.dimension += 1;
+        (f64::from(stratum) + jitter) / f64::from(self.samples_per_pixel)
+    }
+
+    fn get_2d(&mut self) -> [f64; 2] {
+        let [width, height] = self.grid_size;
+        let stratum = self.stratum(width * height);
+        let jitter = [self.jitter(self.dimension), self.jitter(self.dimension + 1)];
+        self.dimension += 2;
+        [
+            (f64::from(stratum % width) + jitter[0]) / f64::from(width),
+            (f64::from(stratum / width) + jitter[1]) / f64::from(height),
+        ]
+    }
+
+    fn clone_box(&self) -> Box<dyn Sampler> {
+        Box::new(*self)
+    }
+}
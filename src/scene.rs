@@ -0,0 +1,138 @@
+use std::{collections::HashMap, sync::Arc};
+
+use rand::{Rng, SeedableRng, rngs::StdRng};
+
+use crate::{
+    hittable::{Hittable, list::HittableList, sphere::Sphere},
+    light::Light,
+    material::{Material, dielectric::Dielectric, lambertian::Lambertian, metal::Metal},
+    sky::{Sky, gradient::Gradient},
+    vec3::{Color, Point3},
+};
+
+/// Everything to render: the objects rays can hit, the lights which have to be sampled and the sky
+/// seen by rays which escape.
+#[derive(Debug)]
+pub struct Scene {
+    world: Box<dyn Hittable + Sync>,
+    lights: Vec<Box<dyn Light>>,
+    sky: Box<dyn Sky>,
+
+    /// ID of every material of the world by its address, numbered from 1 in the order the objects
+    /// using them were added
+    material_ids: HashMap<usize, u32>,
+}
+
+impl Scene {
+    #[must_use]
+    pub fn new(world: Box<dyn Hittable + Sync>) -> Self {
+        let mut material_ids = HashMap::new();
+        world.visit_materials(&mut |material| {
+            let next_id = u32::try_from(material_ids.len() + 1).unwrap_or(u32::MAX);
+            material_ids
+                .entry(address(material.as_ref()))
+                .or_insert(next_id);
+        });
+        Self {
+            world,
+            material_ids,
+            lights: Vec::new(),
+            sky: Box::new(Gradient),
+        }
+    }
+
+    pub fn set_sky(&mut self, sky: Box<dyn Sky>) {
+        self.sky = sky;
+    }
+
+    pub fn add_light(&mut self, light: Box<dyn Light>) {
+        self.lights.push(light);
+    }
+
+    #[must_use]
+    pub fn world(&self) -> &(dyn Hittable + Sync) {
+        self.world.as_ref()
+    }
+
+    #[must_use]
+    pub fn lights(&self) -> &[Box<dyn Light>] {
+        &self.lights
+    }
+
+    #[must_use]
+    pub fn sky(&self) -> &dyn Sky {
+        self.sky.as_ref()
+    }
+
+    /// Returns the ID of a material of the world, which stays the same as long as the objects are
+    /// added in the same order. Returns 0 for materials which aren't part of the world.
+    #[must_use]
+    pub fn material_id(&self, material: &dyn Material) -> u32 {
+        self.material_ids
+            .get(&address(material))
+            .copied()
+            .unwrap_or(0)
+    }
+}
+
+/// The scene on the cover of "Ray Tracing in One Weekend": three large spheres of glass, diffuse
+/// and metal material on a plane of small random spheres, generated from the seed.
+#[must_use]
+pub fn book_cover(seed: u64) -> Scene {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let ground_material = Arc::new(Lambertian::new(Color::new([0.5, 0.5, 0.5])));
+    let material0 = Arc::new(Dielectric::new(1.5));
+    let material1 = Arc::new(Lambertian::new(Color::new([0.4, 0.2, 0.1])));
+    let material2 = Arc::new(Metal::new(Color::new([0.7, 0.6, 0.5]), 0.0));
+
+    let objects: [Box<dyn Hittable + Sync>; 4] = [
+        Box::new(Sphere::new(
+            Point3::new([0.0, -1000.0, 0.0]),
+            1000.0,
+            ground_material,
+        )),
+        Box::new(Sphere::new(Point3::new([0.0, 1.0, 0.0]), 1.0, material0)),
+        Box::new(Sphere::new(Point3::new([-4.0, 1.0, 0.0]), 1.0, material1)),
+        Box::new(Sphere::new(Point3::new([4.0, 1.0, 0.0]), 1.0, material2)),
+    ];
+    let world = HittableList::new(
+        (-11..11)
+            .flat_map(|a| (-11..11).map(move |b| (a, b)))
+            .filter_map(|(a, b)| {
+                let choose_material = rng.random::<f64>();
+                let center = Point3::new([
+                    f64::from(a) + 0.9 * rng.random::<f64>(),
+                    0.2,
+                    f64::from(b) + 0.9 * rng.random::<f64>(),
+                ]);
+                if (center - Point3::new([4.0, 0.2, 0.0])).length() <= 0.9 {
+                    return None;
+                }
+                Some::<Box<dyn Hittable + Sync>>(Box::new(Sphere::new(
+                    center,
+                    0.2,
+                    match choose_material {
+                        // Diffuse
+                        ..0.8 => {
+                            let albedo = Color::random(&mut rng) * Color::random(&mut rng);
+                            Arc::new(Lambertian::new(albedo))
+                        }
+                        ..0.95 => {
+                            let albedo = Color::random_range(&mut rng, 0.5..1.0);
+                            let fuzz = rng.random_range::<f64, _>(0.0..0.5);
+                            Arc::new(Metal::new(albedo, fuzz))
+                        }
+                        _ => Arc::new(Dielectric::new(1.5)),
+                    },
+                )))
+            })
+            .chain(objects)
+            .collect(),
+    );
+    Scene::new(Box::new(world))
+}
+
+/// Address of a material, which identifies it as long as the world holds on to it
+fn address(material: &dyn Material) -> usize {
+    std::ptr::from_ref(material).cast::<()>() as usize
+}
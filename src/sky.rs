@@ -0,0 +1,12 @@
+use std::fmt::Debug;
+
+use crate::vec3::{Color, Vec3};
+
+pub mod gradient;
+pub mod preetham;
+
+/// The light arriving from infinitely far away along rays which don't hit anything.
+pub trait Sky: Debug + Sync + Send {
+    /// Radiance arriving from a unit direction
+    fn radiance(&self, direction: Vec3) -> Color;
+}
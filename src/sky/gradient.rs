@@ -0,0 +1,14 @@
+use crate::vec3::{Color, Vec3};
+
+use super::Sky;
+
+/// A linear blend from white at the bottom to blue at the top.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Gradient;
+
+impl Sky for Gradient {
+    fn radiance(&self, direction: Vec3) -> Color {
+        let a = 0.5 * (direction.y() + 1.0);
+        (1.0 - a) * Color::new([1.0; 3]) + a * Color::new([0.5, 0.7, 1.0])
+    }
+}
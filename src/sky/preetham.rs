@@ -0,0 +1,209 @@
+use std::f64::consts::{FRAC_PI_2, PI};
+
+use crate::{
+    light::directional::DirectionalLight,
+    spectrum,
+    vec3::{Color, Vec3},
+};
+
+use super::Sky;
+
+/// Angular radius of the sun in degrees
+const SUN_RADIUS: f64 = 0.2665;
+
+/// Scale of the radiance by default, bringing a daylight sky of around 10000 candela per square
+/// meter near the brightness of the gradient sky
+const DEFAULT_SCALE: f64 = 1e-4;
+
+/// Surface temperature of the sun in Kelvin
+const SUN_TEMPERATURE: f64 = 5778.0;
+
+/// Coefficients of the Perez sky luminance distribution
+#[derive(Debug, Clone, Copy)]
+struct Perez([f64; 5]);
+
+impl Perez {
+    /// Relative luminance at a zenith angle with the given cosine and an angle to the sun.
+    #[expect(clippy::many_single_char_names)]
+    fn value(&self, cos_theta: f64, gamma: f64) -> f64 {
+        let [a, b, c, d, e] = self.0;
+        let cos_gamma = gamma.cos();
+        (1.0 + a * (b / cos_theta).exp())
+            * (1.0 + c * (d * gamma).exp() + e * cos_gamma * cos_gamma)
+    }
+}
+
+/// The analytic daylight sky model by Preetham, Shirley and Smits, with the sun disk. Y points up,
+/// and colors have the luminance in candela per square meter times the scale.
+#[derive(Debug, Clone, Copy)]
+pub struct Preetham {
+    /// Unit direction towards the sun
+    sun_direction: Vec3,
+
+    /// Zenith angle of the sun in radians
+    sun_theta: f64,
+
+    turbidity: f64,
+
+    /// Luminance and chromaticity x and y at the zenith
+    zenith: [f64; 3],
+
+    /// Distribution coefficients of the luminance and chromaticity x and y
+    distributions: [Perez; 3],
+
+    /// Radiance of the sun disk, or `None` to leave it out
+    sun_radiance: Option<Color>,
+
+    /// Scale applied to all radiance
+    scale: f64,
+}
+
+impl Preetham {
+    /// Creates the sky for a sun at an elevation above the horizon and an azimuth from +Z towards
+    /// +X, both in degrees. The turbidity describes the haziness of the atmosphere, from 2 for a
+    /// very clear to 10 for a hazy sky. The radiance is scaled by 1e-4, so a daytime sky is about
+    /// as bright as the gradient sky under the default display transform.
+    #[must_use]
+    pub fn new(elevation: f64, azimuth: f64, turbidity: f64) -> Self {
+        let (elevation, azimuth) = (elevation.to_radians(), azimuth.to_radians());
+        let sun_direction = Vec3::new([
+            elevation.cos() * azimuth.sin(),
+            elevation.sin(),
+            elevation.cos() * azimuth.cos(),
+        ]);
+        let sun_theta = (FRAC_PI_2 - elevation).clamp(0.0, FRAC_PI_2);
+        let t = turbidity;
+
+        let chi = (4.0 / 9.0 - t / 120.0) * (PI - 2.0 * sun_theta);
+        let zenith_luminance =
+            1000.0 * ((4.0453 * t - 4.9710) * chi.tan() - 0.2155 * t + 2.4192).max(0.0);
+        let theta = [sun_theta.powi(3), sun_theta.powi(2), sun_theta, 1.0];
+        let chromaticity = |coefficients: [[f64; 4]; 3]| {
+            [t * t, t, 1.0]
+                .into_iter()
+                .zip(coefficients)
+                .map(|(factor, row)| {
+                    factor * row.iter().zip(theta).map(|(c, p)| c * p).sum::<f64>()
+                })
+                .sum::<f64>()
+        };
+        let zenith_x = chromaticity([
+            [0.00166, -0.00375, 0.00209, 0.0],
+            [-0.02903, 0.06377, -0.03202, 0.00394],
+            [0.11693, -0.21196, 0.06052, 0.25886],
+        ]);
+        let zenith_y = chromaticity([
+            [0.00275, -0.00610, 0.00317, 0.0],
+            [-0.04214, 0.08970, -0.04153, 0.00516],
+            [0.15346, -0.26756, 0.06670, 0.26688],
+        ]);
+
+        let distributions = [
+            Perez([
+                0.1787 * t - 1.4630,
+                -0.3554 * t + 0.4275,
+                -0.0227 * t + 5.3251,
+                0.1206 * t - 2.5771,
+                -0.0670 * t + 0.3703,
+            ]),
+            Perez([
+                -0.0193 * t - 0.2592,
+                -0.0665 * t + 0.0008,
+                -0.0004 * t + 0.2125,
+                -0.0641 * t - 0.8989,
+                -0.0033 * t + 0.0452,
+            ]),
+            Perez([
+                -0.0167 * t - 0.2608,
+                -0.0950 * t + 0.0092,
+                -0.0079 * t + 0.2102,
+                -0.0441 * t - 1.6537,
+                -0.0109 * t + 0.0529,
+            ]),
+        ];
+
+        Self {
+            sun_direction,
+            sun_theta,
+            turbidity,
+            zenith: [zenith_luminance, zenith_x, zenith_y],
+            distributions,
+            sun_radiance: Some(Self::sun_radiance(sun_theta, turbidity)),
+            scale: DEFAULT_SCALE,
+        }
+    }
+
+    /// Scales all radiance, to bring the sky in range of other light sources. A scale of 1 gives
+    /// the absolute luminance in candela per square meter.
+    #[must_use]
+    pub const fn with_scale(mut self, scale: f64) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Leaves out the sun disk, for when the sun is added to the scene as a light instead.
+    #[must_use]
+    pub const fn without_sun_disk(mut self) -> Self {
+        self.sun_radiance = None;
+        self
+    }
+
+    /// A light matching the sun disk, which samples the sun explicitly instead of relying on rays
+    /// hitting the sky. Use it together with a sky without the sun disk to avoid counting the sun
+    /// twice.
+    #[must_use]
+    pub fn sun_light(&self) -> DirectionalLight {
+        let solid_angle = 2.0 * PI * (1.0 - SUN_RADIUS.to_radians().cos());
+        let radiance = Self::sun_radiance(self.sun_theta, self.turbidity);
+        DirectionalLight::new(
+            self.sun_direction,
+            radiance * solid_angle * self.scale,
+            2.0 * SUN_RADIUS,
+        )
+    }
+
+    /// Radiance of the sun seen through the atmosphere, as a black body attenuated by Rayleigh and
+    /// aerosol scattering along its path.
+    fn sun_radiance(sun_theta: f64, turbidity: f64) -> Color {
+        // Relative optical mass of the air along the path, by Kasten
+        let sun_theta_degrees = sun_theta.to_degrees();
+        let optical_mass =
+            1.0 / (sun_theta.cos() + 0.15 * (93.885 - sun_theta_degrees).powf(-1.253));
+        let beta = 0.046_08 * turbidity - 0.045_86;
+        spectrum::spectrum_to_color(|wavelength| {
+            let micrometers = wavelength / 1000.0;
+            let rayleigh = (-0.008_735 * micrometers.powf(-4.08) * optical_mass).exp();
+            let aerosol = (-beta * micrometers.powf(-1.3) * optical_mass).exp();
+            spectrum::planck(wavelength, SUN_TEMPERATURE) * rayleigh * aerosol
+        })
+    }
+}
+
+impl Sky for Preetham {
+    fn radiance(&self, direction: Vec3) -> Color {
+        let cos_gamma = direction.dot(&self.sun_direction).clamp(-1.0, 1.0);
+        if let Some(sun_radiance) = self.sun_radiance
+            && cos_gamma >= SUN_RADIUS.to_radians().cos()
+        {
+            return sun_radiance * self.scale;
+        }
+
+        // Directions below the horizon get the color of the horizon
+        let cos_theta = direction.y().max(1e-3);
+        let gamma = cos_gamma.acos();
+        let [luminance, x, y] = [0, 1, 2].map(|index| {
+            let distribution = &self.distributions[index];
+            self.zenith[index] * distribution.value(cos_theta, gamma)
+                / distribution.value(1.0, self.sun_theta)
+        });
+        if y <= 0.0 {
+            return Color::default();
+        }
+        let xyz = [x * luminance / y, luminance, (1.0 - x - y) * luminance / y];
+        let mut color = spectrum::xyz_to_color(xyz) * self.scale;
+        for channel in 0..3 {
+            color[channel] = color[channel].max(0.0);
+        }
+        color
+    }
+}
@@ -0,0 +1,116 @@
+//! Conversion of light spectra to colors. Colors are linear sRGB with the luminance in candela per
+//! square meter.
+
+use crate::vec3::Color;
+
+/// Luminous efficacy in lumens per watt of light at the peak of the eye's sensitivity
+pub const MAXIMUM_LUMINOUS_EFFICACY: f64 = 683.0;
+
+/// Range of visible wavelengths in nanometers over which spectra are integrated
+const VISIBLE_WAVELENGTHS: std::ops::RangeInclusive<u32> = 360..=830;
+
+const PLANCK: f64 = 6.626_070_15e-34;
+const SPEED_OF_LIGHT: f64 = 299_792_458.0;
+const BOLTZMANN: f64 = 1.380_649e-23;
+const STEFAN_BOLTZMANN: f64 = 5.670_374_419e-8;
+
+/// Piecewise Gaussian with a different width on either side of its center
+fn gaussian(wavelength: f64, center: f64, width_below: f64, width_above: f64) -> f64 {
+    let width = if wavelength < center {
+        width_below
+    } else {
+        width_above
+    };
+    (-0.5 * ((wavelength - center) / width).powi(2)).exp()
+}
+
+/// The CIE 1931 color matching functions at a wavelength in nanometers, using the multi-lobe fit
+/// by Wyman, Sloan and Shirley.
+#[must_use]
+pub fn color_matching(wavelength: f64) -> [f64; 3] {
+    [
+        1.056 * gaussian(wavelength, 599.8, 37.9, 31.0)
+            + 0.362 * gaussian(wavelength, 442.0, 16.0, 26.7)
+            - 0.065 * gaussian(wavelength, 501.1, 20.4, 26.2),
+        0.821 * gaussian(wavelength, 568.8, 46.9, 40.5)
+            + 0.286 * gaussian(wavelength, 530.9, 16.3, 31.1),
+        1.217 * gaussian(wavelength, 437.0, 11.8, 36.0)
+            + 0.681 * gaussian(wavelength, 459.0, 26.0, 13.8),
+    ]
+}
+
+/// Converts CIE XYZ tristimulus values to linear sRGB.
+#[must_use]
+pub fn xyz_to_color([x, y, z]: [f64; 3]) -> Color {
+    Color::new([
+        3.240_454_2 * x - 1.537_138_5 * y - 0.498_531_4 * z,
+        -0.969_266_0 * x + 1.876_010_8 * y + 0.041_556_0 * z,
+        0.055_643_4 * x - 0.204_025_9 * y + 1.057_225_2 * z,
+    ])
+}
+
+/// Converts a spectral radiance, in watts per steradian per square meter per nanometer as a
+/// function of the wavelength in nanometers, to a color. Colors outside the sRGB gamut are clipped.
+#[must_use]
+pub fn spectrum_to_color(spectral_radiance: impl Fn(f64) -> f64) -> Color {
+    let mut xyz = [0.0; 3];
+    for wavelength in VISIBLE_WAVELENGTHS.map(f64::from) {
+        let radiance = spectral_radiance(wavelength);
+        for (sum, weight) in xyz.iter_mut().zip(color_matching(wavelength)) {
+            *sum += MAXIMUM_LUMINOUS_EFFICACY * weight * radiance;
+        }
+    }
+    let mut color = xyz_to_color(xyz);
+    for channel in 0..3 {
+        color[channel] = color[channel].max(0.0);
+    }
+    color
+}
+
+/// Spectral radiance of a black body by Planck's law, in watts per steradian per square meter per
+/// nanometer.
+#[must_use]
+pub fn planck(wavelength: f64, temperature: f64) -> f64 {
+    let wavelength = wavelength * 1e-9;
+    let radiance = 2.0 * PLANCK * SPEED_OF_LIGHT * SPEED_OF_LIGHT
+        / (wavelength.powi(5)
+            * ((PLANCK * SPEED_OF_LIGHT / (wavelength * BOLTZMANN * temperature)).exp() - 1.0));
+    radiance * 1e-9
+}
+
+/// Color of a black body at the given temperature in Kelvin, scaled to a luminance of 1. Returns
+/// black for temperatures which aren't positive or too low to emit visible light.
+#[must_use]
+pub fn blackbody_color(temperature: f64) -> Color {
+    if !(temperature > 0.0 && temperature.is_finite()) {
+        return Color::default();
+    }
+    let color = spectrum_to_color(|wavelength| planck(wavelength, temperature));
+    let luminance = luminance(color);
+    if luminance > 0.0 && luminance.is_finite() {
+        color / luminance
+    } else {
+        Color::default()
+    }
+}
+
+/// Lumens emitted by a black body at the given temperature per watt of total radiated power.
+/// Returns 0 where `blackbody_color` is black.
+#[must_use]
+pub fn blackbody_luminous_efficacy(temperature: f64) -> f64 {
+    if luminance(blackbody_color(temperature)) == 0.0 {
+        return 0.0;
+    }
+    let luminance: f64 = VISIBLE_WAVELENGTHS
+        .map(f64::from)
+        .map(|wavelength| color_matching(wavelength)[1] * planck(wavelength, temperature))
+        .sum();
+    let radiance = STEFAN_BOLTZMANN * temperature.powi(4) / std::f64::consts::PI;
+    MAXIMUM_LUMINOUS_EFFICACY * luminance / radiance
+}
+
+/// Luminance of a linear sRGB color
+#[must_use]
+pub fn luminance(color: Color) -> f64 {
+    0.212_672_9 * color.x() + 0.715_152_2 * color.y() + 0.072_175_0 * color.z()
+}
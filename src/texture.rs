@@ -0,0 +1,11 @@
+use std::fmt::Debug;
+
+use crate::vec3::{Color, Point3};
+
+pub mod checker;
+pub mod image_texture;
+pub mod solid_color;
+
+pub trait Texture: Debug + Sync + Send {
+    fn value(&self, u: f64, v: f64, point: Point3) -> Color;
+}
@@ -0,0 +1,43 @@
+use std::sync::Arc;
+
+use crate::vec3::{Color, Point3};
+
+use super::{Texture, solid_color::SolidColor};
+
+/// A procedural checker pattern alternating between two textures in surface coordinates.
+#[derive(Debug, Clone)]
+pub struct Checker {
+    /// Number of squares along each surface coordinate
+    scale: f64,
+    even: Arc<dyn Texture>,
+    odd: Arc<dyn Texture>,
+}
+
+impl Checker {
+    #[must_use]
+    pub const fn new(scale: f64, even: Arc<dyn Texture>, odd: Arc<dyn Texture>) -> Self {
+        Self { scale, even, odd }
+    }
+
+    #[must_use]
+    pub fn from_colors(scale: f64, even: Color, odd: Color) -> Self {
+        Self::new(
+            scale,
+            Arc::new(SolidColor::new(even)),
+            Arc::new(SolidColor::new(odd)),
+        )
+    }
+}
+
+impl Texture for Checker {
+    #[expect(clippy::cast_possible_truncation)]
+    fn value(&self, u: f64, v: f64, point: Point3) -> Color {
+        let u_index = (self.scale * u).floor() as i64;
+        let v_index = (self.scale * v).floor() as i64;
+        if (u_index + v_index) % 2 == 0 {
+            self.even.value(u, v, point)
+        } else {
+            self.odd.value(u, v, point)
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use std::{
+    fmt::{self, Debug, Formatter},
+    path::Path,
+};
+
+use image::{ImageResult, Rgb32FImage};
+
+use crate::vec3::{Color, Point3};
+
+use super::Texture;
+
+/// A texture looked up from an image, with u running left to right and v bottom to top.
+#[derive(Clone)]
+pub struct ImageTexture {
+    image: Rgb32FImage,
+
+    /// Hash of the pixels, which stands in for them in the debug representation
+    content_hash: u64,
+}
+
+impl ImageTexture {
+    /// Loads an image holding colors, converting them from sRGB to linear values.
+    ///
+    /// # Errors
+    /// Returns an error if the image couldn't be read or decoded.
+    pub fn open(path: impl AsRef<Path>) -> ImageResult<Self> {
+        let mut image = image::open(path)?.into_rgb32f();
+        image
+            .pixels_mut()
+            .flat_map(|pixel| pixel.0.iter_mut())
+            .for_each(|value| *value = srgb_to_linear(*value));
+        Ok(Self::new(image))
+    }
+
+    /// Loads an image holding data, like a normal or height map, without any color conversion.
+    ///
+    /// # Errors
+    /// Returns an error if the image couldn't be read or decoded.
+    pub fn open_linear(path: impl AsRef<Path>) -> ImageResult<Self> {
+        Ok(Self::new(image::open(path)?.into_rgb32f()))
+    }
+
+    fn new(image: Rgb32FImage) -> Self {
+        Self {
+            content_hash: content_hash(&image),
+            image,
+        }
+    }
+}
+
+impl Debug for ImageTexture {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ImageTexture")
+            .field("width", &self.image.width())
+            .field("height", &self.image.height())
+            .field("content_hash", &format_args!("{:016x}", self.content_hash))
+            .finish()
+    }
+}
+
+/// Hashes the values of the pixels by 64-bit FNV-1a, which is the same for every build.
+fn content_hash(image: &Rgb32FImage) -> u64 {
+    image
+        .iter()
+        .flat_map(|value| value.to_le_bytes())
+        .fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        })
+}
+
+fn srgb_to_linear(value: f32) -> f32 {
+    if value <= 0.040_45 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+impl Texture for ImageTexture {
+    #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn value(&self, u: f64, v: f64, _point: Point3) -> Color {
+        if self.image.width() == 0 || self.image.height() == 0 {
+            // Return solid cyan as a debugging aid for missing texture data
+            return Color::new([0.0, 1.0, 1.0]);
+        }
+
+        // Clamp the coordinates to [0, 1] and flip v to image coordinates
+        let u = u.clamp(0.0, 1.0);
+        let v = 1.0 - v.clamp(0.0, 1.0);
+
+        let x = ((u * f64::from(self.image.width())) as u32).min(self.image.width() - 1);
+        let y = ((v * f64::from(self.image.height())) as u32).min(self.image.height() - 1);
+        let pixel = self.image.get_pixel(x, y);
+        Color::new(pixel.0.map(f64::from))
+    }
+}
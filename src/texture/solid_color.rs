@@ -0,0 +1,21 @@
+use crate::vec3::{Color, Point3};
+
+use super::Texture;
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SolidColor {
+    albedo: Color,
+}
+
+impl SolidColor {
+    #[must_use]
+    pub const fn new(albedo: Color) -> Self {
+        Self { albedo }
+    }
+}
+
+impl Texture for SolidColor {
+    fn value(&self, _u: f64, _v: f64, _point: Point3) -> Color {
+        self.albedo
+    }
+}
@@ -1,5 +1,6 @@
 use std::{
     array,
+    f64::consts::PI,
     fmt::Display,
     iter::Sum,
     ops::{
@@ -7,8 +8,7 @@ use std::{
     },
 };
 
-use image::Rgb;
-use rand::{random, random_range};
+use rand::Rng;
 
 #[derive(Debug, Default, Clone, Copy)]
 pub struct Vec3([f64; 3]);
@@ -67,20 +67,31 @@ impl Vec3 {
         self / self.length()
     }
 
+    /// Returns a unit vector perpendicular to this unit vector.
     #[must_use]
-    pub fn random() -> Self {
-        Self(array::from_fn(|_| random()))
+    pub fn perpendicular(&self) -> Self {
+        let axis = if self.x().abs() > 0.9 {
+            Self::new([0.0, 1.0, 0.0])
+        } else {
+            Self::new([1.0, 0.0, 0.0])
+        };
+        self.cross(&axis).unit_vector()
+    }
+
+    #[must_use]
+    pub fn random(rng: &mut impl Rng) -> Self {
+        Self(array::from_fn(|_| rng.random()))
     }
 
     #[must_use]
-    pub fn random_range(range: Range<f64>) -> Self {
-        Self(array::from_fn(|_| random_range(range.clone())))
+    pub fn random_range(rng: &mut impl Rng, range: Range<f64>) -> Self {
+        Self(array::from_fn(|_| rng.random_range(range.clone())))
     }
 
     #[must_use]
-    pub fn random_unit_vector() -> Self {
+    pub fn random_unit_vector(rng: &mut impl Rng) -> Self {
         loop {
-            let point = Self::random_range(-1.0..1.0);
+            let point = Self::random_range(rng, -1.0..1.0);
             let length_squared = point.length_squared();
             if 1e-160 < length_squared && length_squared <= 1.0 {
                 return point / length_squared.sqrt();
@@ -88,9 +99,34 @@ impl Vec3 {
         }
     }
 
+    /// Maps two uniform numbers in [0, 1) to a unit vector uniformly distributed over the sphere.
+    #[must_use]
+    pub fn sample_unit_vector([u1, u2]: [f64; 2]) -> Self {
+        let z = 1.0 - 2.0 * u1;
+        let radius = (1.0 - z * z).max(0.0).sqrt();
+        let phi = 2.0 * PI * u2;
+        Self([radius * phi.cos(), radius * phi.sin(), z])
+    }
+
+    /// Maps two uniform numbers in [0, 1) to a point uniformly distributed in the unit disk,
+    /// keeping neighboring numbers close together.
+    #[must_use]
+    pub fn sample_in_unit_disk([u1, u2]: [f64; 2]) -> Self {
+        let (x, y) = (2.0 * u1 - 1.0, 2.0 * u2 - 1.0);
+        if x == 0.0 && y == 0.0 {
+            return Self::default();
+        }
+        let (radius, theta) = if x.abs() > y.abs() {
+            (x, PI / 4.0 * (y / x))
+        } else {
+            (y, PI / 2.0 - PI / 4.0 * (x / y))
+        };
+        Self([radius * theta.cos(), radius * theta.sin(), 0.0])
+    }
+
     #[must_use]
-    pub fn random_on_hemisphere(&self) -> Self {
-        let on_unit_sphere = Self::random_unit_vector();
+    pub fn random_on_hemisphere(&self, rng: &mut impl Rng) -> Self {
+        let on_unit_sphere = Self::random_unit_vector(rng);
         if self.dot(&on_unit_sphere) > 0.0 {
             on_unit_sphere
         } else {
@@ -117,9 +153,13 @@ impl Vec3 {
     }
 
     #[must_use]
-    pub fn random_in_unit_disk() -> Vec3 {
+    pub fn random_in_unit_disk(rng: &mut impl Rng) -> Vec3 {
         loop {
-            let point = Vec3::new([random_range(-1.0..1.0), random_range(-1.0..1.0), 0.0]);
+            let point = Vec3::new([
+                rng.random_range(-1.0..1.0),
+                rng.random_range(-1.0..1.0),
+                0.0,
+            ]);
             if point.length_squared() < 1.0 {
                 return point;
             }
@@ -230,6 +270,15 @@ impl DivAssign for Vec3 {
     }
 }
 
+impl Div for Vec3 {
+    type Output = Self;
+
+    fn div(mut self, rhs: Self) -> Self::Output {
+        self /= rhs;
+        self
+    }
+}
+
 impl Div<f64> for Vec3 {
     type Output = Self;
 
@@ -255,23 +304,3 @@ impl Display for Vec3 {
 pub type Point3 = Vec3;
 
 pub type Color = Vec3;
-
-fn linear_to_gamma(linear_component: f64) -> f64 {
-    if linear_component > 0.0 {
-        linear_component.sqrt()
-    } else {
-        0.0
-    }
-}
-
-impl From<Color> for Rgb<u8> {
-    #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
-    fn from(value: Color) -> Self {
-        // Translate the [0, 1) component values to the byte range [0, 255]
-        const MAXIMUM: f64 = 0.999;
-        let red = (256.0 * linear_to_gamma(value.x()).clamp(0.0, MAXIMUM)) as u8;
-        let green = (256.0 * linear_to_gamma(value.y()).clamp(0.0, MAXIMUM)) as u8;
-        let blue = (256.0 * linear_to_gamma(value.z()).clamp(0.0, MAXIMUM)) as u8;
-        Self([red, green, blue])
-    }
-}